@@ -1,70 +1,70 @@
-extern crate rand;
+mod park_miller;
 
-use self::rand::{Rng, rng};
+pub(crate) use park_miller::ParkMiller;
 
-use crate::piece::{PieceType, UNIQUE_TYPES};
+use crate::Randomizer;
+use crate::piece::PieceType;
+
+use std::collections::VecDeque;
+
+/// The order TETR.IO lists pieces in before shuffling a bag
+const TETRIO_ORDER: [PieceType; 7] = [
+    PieceType::Z,
+    PieceType::L,
+    PieceType::O,
+    PieceType::S,
+    PieceType::I,
+    PieceType::J,
+    PieceType::T,
+];
 
 #[derive(Debug)]
 pub struct Bag {
     size: u32,
-    curr_pieces: u32,
-    unique_pieces_remaining: u8,
-    pieces: [PieceType; 7],
-    piece_counts: [u32; 7],
+    pieces: VecDeque<PieceType>,
+    rng: ParkMiller,
 }
 
 impl Bag {
     #[must_use]
-    /// Creates a new, empty bag of size `size`
-    pub fn new(size: u32) -> Bag {
+    /// Creates a new, empty bag of size `size`, which is shuffled by a
+    /// generator seeded with `seed`. Bags of size 7 draw the same pieces as
+    /// TETR.IO does for the same seed.
+    pub fn new(size: u32, seed: u64) -> Bag {
         Bag {
             size,
-            curr_pieces: 0,
-            unique_pieces_remaining: 0,
-            pieces: UNIQUE_TYPES,
-            piece_counts: [0; 7],
+            pieces: VecDeque::new(),
+            rng: ParkMiller::new(seed),
         }
     }
 
-    /// Draws a random piece from the remaining pieces in the bag.
+    /// Draws the next piece from the bag.
     /// Refills the bag if it is empty.
     pub fn draw(&mut self) -> PieceType {
         if self.empty() {
             self.fill();
         }
 
-        let chosen_idx = rng().random_range(0..self.unique_pieces_remaining) as usize;
-        self.piece_counts[chosen_idx] -= 1;
-        self.curr_pieces -= 1;
-
-        if self.piece_counts[chosen_idx] == 0 {
-            self.unique_pieces_remaining -= 1;
-
-            let new_idx = self.unique_pieces_remaining as usize;
-            self.pieces.swap(chosen_idx, new_idx);
-            self.piece_counts.swap(chosen_idx, new_idx);
-
-            return self.pieces[new_idx];
-        }
-
-        self.pieces[chosen_idx]
+        self.pieces
+            .pop_front()
+            .expect("A filled bag should contain at least one piece")
     }
 
-    /// Fills the bag with `self.size` pieces
+    /// Fills the bag with `self.size` pieces, by shuffling enough copies of
+    /// every piece and keeping the first `self.size`
     fn fill(&mut self) {
-        let initial_count = self.size / 7 + u32::from(self.size % 7 != 0);
+        let copies = self.size.div_ceil(7) as usize;
+        let mut pieces: Vec<PieceType> = TETRIO_ORDER.repeat(copies);
 
-        for i in self.unique_pieces_remaining as usize..7 {
-            self.piece_counts[i] = initial_count;
-        }
-        self.curr_pieces = self.size;
-        self.unique_pieces_remaining = 7;
+        self.rng.shuffle(&mut pieces);
+        self.pieces
+            .extend(pieces.into_iter().take(self.size as usize));
     }
 
     #[must_use]
     /// Returns `true` if the bag is empty, `false` otherwise
     pub fn empty(&self) -> bool {
-        self.curr_pieces == 0
+        self.pieces.is_empty()
     }
 
     #[must_use]
@@ -74,6 +74,12 @@ impl Bag {
     }
 }
 
+impl Randomizer for Bag {
+    fn draw(&mut self) -> PieceType {
+        Bag::draw(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Bag;
@@ -81,7 +87,7 @@ mod tests {
 
     #[test]
     fn bag_emptying() {
-        let mut my_bag = Bag::new(7);
+        let mut my_bag = Bag::new(7, 0);
         assert!(my_bag.empty(), "bag should start out empty");
         for _ in 0..7 {
             my_bag.draw();
@@ -94,7 +100,7 @@ mod tests {
 
     #[test]
     fn bag_drawing() {
-        let mut my_bag = Bag::new(21);
+        let mut my_bag = Bag::new(21, 0);
         let mut piece_counts = HashMap::new();
         for _ in 0..21 {
             *(piece_counts.entry(my_bag.draw()).or_insert(0)) += 1;
@@ -107,4 +113,19 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn seeded_bags_are_deterministic() {
+        let draw = |seed| {
+            let mut bag = Bag::new(7, seed);
+            (0..21).map(|_| bag.draw()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(draw(12345), draw(12345));
+        assert_ne!(
+            draw(12345),
+            draw(54321),
+            "Different seeds should draw different pieces"
+        );
+    }
 }
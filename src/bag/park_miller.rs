@@ -0,0 +1,84 @@
+/// The modulus of the generator, the Mersenne prime 2^31 - 1
+const MODULUS: u64 = 2_147_483_647;
+/// The multiplier of the generator, as in the minimal standard generator
+const MULTIPLIER: u64 = 16_807;
+
+/// A Park-Miller random number generator, which is portable and matches the
+/// generator TETR.IO uses for its piece queue
+#[derive(Debug, Clone)]
+pub struct ParkMiller {
+    state: u64,
+}
+
+impl ParkMiller {
+    #[must_use]
+    /// Creates a generator from `seed` the same way TETR.IO does
+    pub fn new(seed: u64) -> ParkMiller {
+        let state = seed % MODULUS;
+        ParkMiller {
+            state: if state == 0 { MODULUS - 1 } else { state },
+        }
+    }
+
+    /// Advances the generator, returning its new state, which is always in
+    /// `1..MODULUS`
+    pub fn next(&mut self) -> u64 {
+        self.state = self.state * MULTIPLIER % MODULUS;
+        self.state
+    }
+
+    /// Returns a random number in `0.0..1.0`, matching TETR.IO's `nextFloat`
+    pub fn next_float(&mut self) -> f64 {
+        let numerator = u32::try_from(self.next() - 1).unwrap_or(u32::MAX);
+        let denominator = u32::try_from(MODULUS - 1).unwrap_or(u32::MAX);
+        f64::from(numerator) / f64::from(denominator)
+    }
+
+    /// Returns `true` with probability `chance`, `false` otherwise
+    pub fn next_bool(&mut self, chance: f64) -> bool {
+        self.next_float() < chance
+    }
+
+    /// Returns a random index in `0..len`, calculated with integers but equal
+    /// to TETR.IO's `floor(nextFloat() * len)`
+    pub fn next_index(&mut self, len: usize) -> usize {
+        let index = (self.next() - 1) * len as u64 / (MODULUS - 1);
+        usize::try_from(index).unwrap_or(len - 1)
+    }
+
+    /// Shuffles `items` with a Fisher-Yates shuffle from the end of the slice,
+    /// matching TETR.IO's `shuffleArray`
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next_index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_standard_sequence() {
+        let mut rng = ParkMiller::new(1);
+        for _ in 0..9999 {
+            rng.next();
+        }
+        assert_eq!(
+            1_043_618_065,
+            rng.next(),
+            "The 10000th value from seed 1 should match the minimal standard generator"
+        );
+    }
+
+    #[test]
+    fn zero_seed() {
+        assert_eq!(
+            ParkMiller::new(MODULUS - 1).next(),
+            ParkMiller::new(0).next(),
+            "A seed of 0 should be treated as 2^31 - 2"
+        );
+    }
+}
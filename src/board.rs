@@ -1,4 +1,12 @@
+mod cell;
 mod drop;
+mod garbage;
+mod placement;
+
+pub use cell::Cell;
+pub use placement::{PlacementResult, Spin};
+
+use crate::Position;
 
 use std::fmt::{Display, Error, Formatter};
 use std::ops::Index;
@@ -6,7 +14,7 @@ use std::ops::Index;
 /// Represents a 10x20 Tetris game board, although the implementation height is
 /// 24, the maximum height a piece can be placed.
 pub struct Board {
-    board: [[Option<()>; 10]; 24],
+    board: [[Cell; 10]; 24],
     column_heights: [u8; 10],
 }
 
@@ -15,7 +23,7 @@ impl Board {
     /// Creates a new board with all cells empty
     pub fn new() -> Board {
         Board {
-            board: [[None; 10]; 24],
+            board: [[Cell::Empty; 10]; 24],
             column_heights: [0; 10],
         }
     }
@@ -30,12 +38,74 @@ impl Board {
         24
     }
 
-    pub fn cells(&self) -> &[[Option<()>; 10]; 24] {
+    #[must_use]
+    /// Returns the cells of the board, indexed by row then column
+    pub fn cells(&self) -> &[[Cell; 10]; 24] {
         &self.board
     }
 
     #[must_use]
-    /// Returns the string representation of a row of the board
+    /// Returns `true` if `pos` is outside of the board or filled, `false`
+    /// otherwise
+    pub fn is_occupied(&self, pos: Position) -> bool {
+        match pos.indices() {
+            Some((x, y)) => {
+                x >= usize::from(self.width())
+                    || y >= usize::from(self.height())
+                    || self.board[y][x].is_filled()
+            }
+            None => true,
+        }
+    }
+
+    #[must_use]
+    /// Returns the height of each column, that is the number of rows from the
+    /// floor up to and including the column's highest filled cell
+    pub fn column_heights(&self) -> [u8; 10] {
+        self.column_heights
+    }
+
+    #[must_use]
+    /// Returns `true` if every cell of the board is empty, `false` otherwise.
+    /// This only checks the column heights, so takes constant time.
+    pub fn is_empty(&self) -> bool {
+        self.column_heights.iter().all(|&height| height == 0)
+    }
+
+    #[must_use]
+    /// Returns `true` if the stored column heights match the highest filled
+    /// cell of every column in `cells()`, `false` otherwise
+    pub fn column_heights_valid(&self) -> bool {
+        (0..usize::from(self.width()))
+            .all(|col| self.column_heights[col] == self.compute_column_height(col))
+    }
+
+    /// Recomputes the heights of `columns` from the board's cells, then checks
+    /// in debug builds that every column height matches the board's cells
+    fn recompute_column_heights(&mut self, columns: impl IntoIterator<Item = usize>) {
+        for col in columns {
+            self.column_heights[col] = self.compute_column_height(col);
+        }
+
+        debug_assert!(
+            self.column_heights_valid(),
+            "Column heights {:?} do not match the board's cells",
+            self.column_heights,
+        );
+    }
+
+    /// Computes the height of column `col` by scanning its cells from the top
+    fn compute_column_height(&self, col: usize) -> u8 {
+        self.board
+            .iter()
+            .zip((1..=self.height()).rev())
+            .find(|(row, _)| row[col].is_filled())
+            .map_or(0, |(_, height)| height)
+    }
+
+    #[must_use]
+    /// Returns the string representation of a row of the board, with each
+    /// cell displayed as its glyph
     ///
     /// # Panics
     ///
@@ -48,17 +118,7 @@ impl Board {
             self.height(),
         );
 
-        let mut row_string = String::new();
-
-        for col in 0..self.width() as usize {
-            row_string.push(if self.board[row][col].is_some() {
-                '■'
-            } else {
-                ' '
-            });
-        }
-
-        row_string
+        self.board[row].iter().map(|cell| cell.glyph()).collect()
     }
 }
 
@@ -81,9 +141,20 @@ impl Display for Board {
 }
 
 impl Index<usize> for Board {
-    type Output = [Option<()>; 10];
+    type Output = [Cell; 10];
 
     fn index(&self, row: usize) -> &Self::Output {
         &self.board[row]
     }
 }
+
+#[cfg(test)]
+impl Board {
+    /// Fills the cell at column `x` and row `y` with garbage, raising the
+    /// column's height if needed
+    pub fn fill_cell(&mut self, x: usize, y: usize) {
+        self.board[y][x] = Cell::Garbage;
+        self.column_heights[x] =
+            self.column_heights[x].max(self.height() - u8::try_from(y).unwrap());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::PieceType;
+
+/// The contents of a single cell of the board
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum Cell {
+    /// Nothing fills the cell
+    #[default]
+    Empty,
+    /// Part of a locked piece of the contained type fills the cell
+    Piece(PieceType),
+    /// Part of a garbage line fills the cell
+    Garbage,
+}
+
+impl Cell {
+    #[must_use]
+    /// Returns `true` if the cell is filled, `false` if it is empty
+    pub const fn is_filled(self) -> bool {
+        !matches!(self, Cell::Empty)
+    }
+
+    #[must_use]
+    /// Returns `true` if the cell is empty, `false` if it is filled
+    pub const fn is_empty(self) -> bool {
+        matches!(self, Cell::Empty)
+    }
+
+    #[must_use]
+    /// Returns the character the cell is displayed as, which is the letter of
+    /// the piece that filled it, or a shaded block for garbage
+    pub const fn glyph(self) -> char {
+        match self {
+            Cell::Empty => ' ',
+            Cell::Piece(PieceType::S) => 'S',
+            Cell::Piece(PieceType::Z) => 'Z',
+            Cell::Piece(PieceType::L) => 'L',
+            Cell::Piece(PieceType::J) => 'J',
+            Cell::Piece(PieceType::T) => 'T',
+            Cell::Piece(PieceType::I) => 'I',
+            Cell::Piece(PieceType::O) => 'O',
+            Cell::Garbage => '▒',
+        }
+    }
+}
@@ -1,60 +1,82 @@
-use crate::{Board, Piece, TetrisError};
+use super::Cell;
+use crate::{Board, Piece, PlacementResult, Position, SpinRule, TetrisError};
 
 impl Board {
     /// Hard drops the passed piece, consuming it, then updates the board to
-    /// represent the placed piece.
-    pub fn hard_drop(&mut self, mut piece: Piece) -> Result<(), TetrisError> {
-        piece.hard_drop(self.column_heights)?;
+    /// represent the placed piece. Returns the result of the placement, with
+    /// spins detected according to `spin_rule`.
+    pub fn hard_drop(
+        &mut self,
+        mut piece: Piece,
+        spin_rule: SpinRule,
+    ) -> Result<PlacementResult, TetrisError> {
+        piece.hard_drop(self)?;
+        self.lock(&piece, spin_rule)
+    }
 
-        for pos in piece.get_pos_mask() {
-            self.board[pos.y() as usize][pos.x() as usize] = Some(());
+    /// Locks the passed piece in place, then updates the board to represent
+    /// the placed piece. Returns the result of the placement,
+    /// with spins detected according to `spin_rule`, or
+    /// `Err(TetrisError::InvalidPlacement)` if the piece is outside of the
+    /// board or overlaps an occupied cell.
+    pub fn lock(
+        &mut self,
+        piece: &Piece,
+        spin_rule: SpinRule,
+    ) -> Result<PlacementResult, TetrisError> {
+        if piece.collides(self) {
+            return Err(TetrisError::InvalidPlacement);
         }
 
-        for (i, y_offset) in piece.upper_edge().into_iter().flatten() {
-            // -piece.y() + 23 converts piece y-coordinate (where higher is
-            // lower on the board because arrays) to height, actual y-coordinate
-            self.column_heights[(piece.x() + i as i32) as usize] =
-                (-piece.y() + 23) as u8 - y_offset + 1;
+        let spin = piece.spin(self, spin_rule);
+        let cells: Vec<(usize, usize)> = piece
+            .get_pos_mask()
+            .iter()
+            .filter_map(Position::indices)
+            .collect();
+        for &(x, y) in &cells {
+            self.board[y][x] = Cell::Piece(piece.kind());
         }
+        self.recompute_column_heights(cells.iter().map(|&(x, _)| x));
 
-        let lines_cleared = self.clear_lines(piece);
+        let cleared_rows = self.clear_lines(piece);
+        let perfect_clear = self.is_empty();
 
-        Ok(())
+        Ok(PlacementResult::new(
+            piece,
+            spin,
+            cleared_rows,
+            perfect_clear,
+        ))
     }
 
-    fn clear_lines(&mut self, piece: Piece) -> u8 {
-        let mut lines_cleared: u8 = 0;
-
-        for (i, edge) in piece.left_edge().iter().rev().enumerate() {
-            let row = (piece.y() + 3) as usize - i;
-
-            if edge.is_some() && self.board[row].iter().all(|cell| cell.is_some()) {
-                lines_cleared += 1;
-                for cell in self.board[row].iter_mut() {
-                    *cell = None;
-                }
-            } else if row < self.height() as usize && lines_cleared > 0 {
-                for i in 0..self.width() as usize {
-                    self.board[row + lines_cleared as usize][i] = self.board[row][i];
-                }
-            }
-        }
-
-        if lines_cleared > 0 {
-            for row in (0..piece.y() as usize).rev() {
-                for i in 0..self.width() as usize {
-                    self.board[row + lines_cleared as usize][i] = self.board[row][i];
-                }
-            }
+    /// Clears the full rows among those occupied by `piece`, shifting the rows
+    /// above them down. Returns the cleared rows from top to bottom.
+    fn clear_lines(&mut self, piece: &Piece) -> Vec<usize> {
+        let mut rows: Vec<usize> = piece
+            .get_pos_mask()
+            .iter()
+            .filter_map(|pos| usize::try_from(pos.y()).ok())
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows.retain(|&row| self.board[row].iter().all(|cell| cell.is_filled()));
 
-            for col in self.column_heights.iter_mut() {
-                *col -= lines_cleared;
+        // Clearing from top to bottom keeps the indices of the lower rows valid
+        for &row in &rows {
+            for y in (1..=row).rev() {
+                self.board[y] = self.board[y - 1];
             }
+            self.board[0] = [Cell::Empty; 10];
         }
-        if lines_cleared > 0 {
-            println!("{lines_cleared}");
+
+        // Overhangs and gaps mean a clear can lower each column by anything
+        // from zero to every cleared row, so recompute them from the cells
+        if !rows.is_empty() {
+            self.recompute_column_heights(0..usize::from(self.width()));
         }
-        return lines_cleared;
+
+        rows
     }
 }
 
@@ -62,7 +84,14 @@ impl Board {
 mod tests {
     use super::*;
 
-    use crate::PieceType;
+    use crate::{Orientation, PieceType, Position};
+
+    /// Creates a flat I piece moved to the rightmost four columns
+    fn right_i_piece(board: &Board) -> Piece {
+        let mut piece = Piece::new(PieceType::I);
+        while piece.move_right(board).is_ok() {}
+        piece
+    }
 
     #[test]
     fn empty_board_drop_flat_i_center() {
@@ -70,7 +99,7 @@ mod tests {
         let mut correct_col_heights = [0u8; 10];
 
         for i in 0..22 {
-            let _ = board.hard_drop(Piece::new(PieceType::I));
+            let _ = board.hard_drop(Piece::new(PieceType::I), SpinRule::default());
             correct_col_heights[3] += 1;
             correct_col_heights[4] += 1;
             correct_col_heights[5] += 1;
@@ -86,4 +115,183 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn placement_result_line_clear() {
+        let mut board = Board::new();
+        for x in 0..6 {
+            board.fill_cell(x, 23);
+            board.fill_cell(x, 22);
+        }
+
+        let piece = right_i_piece(&board);
+        let placement = board.hard_drop(piece, SpinRule::default()).unwrap();
+
+        assert_eq!(PieceType::I, placement.kind());
+        assert_eq!(Orientation::North, placement.orientation());
+        assert_eq!(
+            Position::at(6, 22),
+            placement.position(),
+            "Placement should report the final position of the piece"
+        );
+        assert_eq!(
+            &[
+                Position::at(6, 23),
+                Position::at(7, 23),
+                Position::at(8, 23),
+                Position::at(9, 23),
+            ],
+            placement.cells(),
+            "Placement should report the cells filled by the piece"
+        );
+        assert_eq!(
+            &[23],
+            placement.cleared_rows(),
+            "Placement should report the cleared rows"
+        );
+        assert!(!placement.perfect_clear());
+
+        assert!(
+            board[23][..6].iter().all(|cell| cell.is_filled())
+                && board[23][6..].iter().all(|cell| cell.is_empty()),
+            "Rows above a cleared row should be shifted down"
+        );
+        assert!(board[22].iter().all(|cell| cell.is_empty()));
+    }
+
+    #[test]
+    fn placement_result_perfect_clear() {
+        let mut board = Board::new();
+        for x in 0..6 {
+            board.fill_cell(x, 23);
+        }
+
+        let piece = right_i_piece(&board);
+        let placement = board.hard_drop(piece, SpinRule::default()).unwrap();
+
+        assert_eq!(&[23], placement.cleared_rows());
+        assert!(
+            placement.perfect_clear(),
+            "Clearing every filled cell should be reported as a perfect clear"
+        );
+    }
+
+    #[test]
+    fn board_is_empty() {
+        let mut board = Board::new();
+        assert!(board.is_empty(), "A new board should be empty");
+
+        for x in 0..6 {
+            board.fill_cell(x, 23);
+        }
+        board.fill_cell(0, 22);
+        assert!(!board.is_empty());
+
+        let placement = board
+            .hard_drop(right_i_piece(&board), SpinRule::default())
+            .unwrap();
+        assert!(
+            !board.is_empty() && !placement.perfect_clear(),
+            "Cells left above a cleared row should not be a perfect clear"
+        );
+
+        for x in 1..6 {
+            board.fill_cell(x, 23);
+        }
+        board
+            .hard_drop(right_i_piece(&board), SpinRule::default())
+            .unwrap();
+        assert!(
+            board.is_empty(),
+            "Clearing the last filled row should empty the board"
+        );
+    }
+
+    #[test]
+    fn lock_under_overhang_keeps_column_heights() {
+        let mut board = Board::new();
+        let mut piece = Piece::new(PieceType::O);
+        while piece.move_left(&board).is_ok() {}
+        while piece.move_down(&board).is_ok() {}
+        for x in 0..3 {
+            board.fill_cell(x, 20);
+        }
+        board.lock(&piece, SpinRule::default()).unwrap();
+
+        assert_eq!(
+            [4, 4, 4, 0, 0, 0, 0, 0, 0, 0],
+            board.column_heights,
+            "A piece locked under an overhang should not lower the column heights"
+        );
+        assert!(board.column_heights_valid());
+    }
+
+    #[test]
+    fn clear_with_gaps_recomputes_column_heights() {
+        let mut board = Board::new();
+        for x in 0..9 {
+            board.fill_cell(x, 23);
+            board.fill_cell(x, 21);
+        }
+        board.fill_cell(0, 22);
+
+        let mut piece = Piece::new(PieceType::I);
+        piece.rotate_cw(&board, crate::KickTable::Srs).unwrap();
+        while piece.move_right(&board).is_ok() {}
+        let placement = board.hard_drop(piece, SpinRule::default()).unwrap();
+
+        assert_eq!(&[21, 23], placement.cleared_rows());
+        assert_eq!(
+            [1, 0, 0, 0, 0, 0, 0, 0, 0, 2],
+            board.column_heights,
+            "Columns should only keep the height of the cells left after the clear"
+        );
+        assert!(board.column_heights_valid());
+    }
+
+    #[test]
+    fn hard_drop_under_overhang() {
+        let mut board = Board::new();
+        let mut piece = Piece::new(PieceType::O);
+        while piece.move_left(&board).is_ok() {}
+        while piece.y() < 19 {
+            piece.move_down(&board).unwrap();
+        }
+        for x in 0..4 {
+            board.fill_cell(x, 18);
+        }
+
+        let placement = board.hard_drop(piece, SpinRule::default()).unwrap();
+
+        assert_eq!(
+            Position::at(-1, 22),
+            placement.position(),
+            "A piece under an overhang should drop to the floor, not above the overhang"
+        );
+        assert_eq!([6, 6, 6, 6, 0, 0, 0, 0, 0, 0], board.column_heights);
+        assert!(board[22][0].is_filled() && board[23][1].is_filled());
+    }
+
+    #[test]
+    fn locked_cells_remember_their_piece() {
+        let mut board = Board::new();
+        board.fill_cell(0, 23);
+        board
+            .hard_drop(Piece::new(PieceType::T), SpinRule::default())
+            .unwrap();
+
+        assert_eq!(
+            [Cell::Empty, Cell::Piece(PieceType::T), Cell::Empty],
+            [board[22][3], board[22][4], board[22][5]]
+        );
+        assert_eq!(
+            [Cell::Piece(PieceType::T); 3],
+            [board[23][3], board[23][4], board[23][5]]
+        );
+        assert_eq!(
+            "▒  TTT    ",
+            board.row_to_string(23),
+            "Rows should display each cell as its glyph"
+        );
+    }
 }
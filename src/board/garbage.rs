@@ -0,0 +1,84 @@
+use super::Cell;
+use crate::Board;
+
+impl Board {
+    /// Inserts `lines` rows of garbage at the bottom of the board, each filled
+    /// except for column `hole`, shifting the rest of the board up. Returns
+    /// `true` if any filled cells were pushed off the top of the board,
+    /// `false` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hole` is out of bounds, that is if `hole >= 10`, the width
+    /// of a Tetris board.
+    pub fn insert_garbage(&mut self, lines: usize, hole: usize) -> bool {
+        assert!(
+            hole < usize::from(self.width()),
+            "Index out of bounds: attempted to put a garbage hole in column {hole}, but only {} \
+            exist",
+            self.width(),
+        );
+
+        let height = usize::from(self.height());
+        let lines = lines.min(height);
+        let overflowed = self.board[..lines]
+            .iter()
+            .flatten()
+            .any(|cell| cell.is_filled());
+
+        let mut garbage_row = [Cell::Garbage; 10];
+        garbage_row[hole] = Cell::Empty;
+
+        self.board.copy_within(lines.., 0);
+        self.board[height - lines..].fill(garbage_row);
+
+        self.recompute_column_heights(0..usize::from(self.width()));
+
+        overflowed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn garbage_shifts_stack_up() {
+        let mut board = Board::new();
+        board.fill_cell(0, 23);
+        board.fill_cell(5, 20);
+
+        assert!(!board.insert_garbage(2, 3));
+        assert!(
+            board[21][0].is_filled() && board[18][5].is_filled(),
+            "The stack should be shifted up by the number of garbage lines"
+        );
+        for row in 22..24 {
+            assert!(
+                (0..10).all(|col| board[row][col].is_filled() == (col != 3)),
+                "Garbage row {row} should be filled except for the hole in column 3"
+            );
+        }
+        assert_eq!([3, 2, 2, 0, 2, 6, 2, 2, 2, 2], board.column_heights());
+        assert_eq!(
+            Cell::Garbage,
+            board[23][0],
+            "Garbage rows should be filled with garbage cells"
+        );
+    }
+
+    #[test]
+    fn garbage_overflow() {
+        let mut board = Board::new();
+        board.fill_cell(4, 1);
+
+        assert!(
+            !board.insert_garbage(1, 0),
+            "Pushing a cell into the top row should not overflow the board"
+        );
+        assert!(
+            board.insert_garbage(1, 0),
+            "Pushing a cell off the top of the board should overflow"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{Orientation, Piece, PieceType, Position};
+
+/// The kind of spin a piece was locked with
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Spin {
+    /// The piece was not spun into place
+    #[default]
+    None,
+    /// A spin that only just meets the requirements, such as a T-spin mini
+    Mini,
+    /// A full spin, such as a T-spin
+    Full,
+}
+
+/// The outcome of locking a piece on the board
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PlacementResult {
+    kind: PieceType,
+    position: Position,
+    orientation: Orientation,
+    cells: [Position; 4],
+    spin: Spin,
+    cleared_rows: Vec<usize>,
+    perfect_clear: bool,
+    combo: Option<u32>,
+    back_to_back: Option<u32>,
+    attack: u32,
+    surge: u32,
+    sent: u32,
+}
+
+impl PlacementResult {
+    #[must_use]
+    /// Creates the result of locking `piece` with `spin`, which cleared
+    /// `cleared_rows`
+    pub fn new(
+        piece: &Piece,
+        spin: Spin,
+        cleared_rows: Vec<usize>,
+        perfect_clear: bool,
+    ) -> PlacementResult {
+        PlacementResult {
+            kind: piece.kind(),
+            position: *piece.position(),
+            orientation: piece.orientation(),
+            cells: piece.get_pos_mask(),
+            spin,
+            cleared_rows,
+            perfect_clear,
+            combo: None,
+            back_to_back: None,
+            attack: 0,
+            surge: 0,
+            sent: 0,
+        }
+    }
+
+    #[must_use]
+    /// Returns the type of the locked piece
+    pub fn kind(&self) -> PieceType {
+        self.kind
+    }
+
+    #[must_use]
+    /// Returns the final position of the locked piece
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    #[must_use]
+    /// Returns the final orientation of the locked piece
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    #[must_use]
+    /// Returns the positions of the cells filled by the locked piece, before
+    /// any lines were cleared
+    pub fn cells(&self) -> &[Position; 4] {
+        &self.cells
+    }
+
+    #[must_use]
+    /// Returns the kind of spin the piece was locked with
+    pub fn spin(&self) -> Spin {
+        self.spin
+    }
+
+    #[must_use]
+    /// Returns the rows that were cleared, from top to bottom, as they were
+    /// numbered before clearing
+    pub fn cleared_rows(&self) -> &[usize] {
+        &self.cleared_rows
+    }
+
+    #[must_use]
+    /// Returns the number of lines cleared
+    pub fn lines_cleared(&self) -> usize {
+        self.cleared_rows.len()
+    }
+
+    #[must_use]
+    /// Returns `true` if the placement left the board empty, `false` otherwise
+    pub fn perfect_clear(&self) -> bool {
+        self.perfect_clear
+    }
+
+    #[must_use]
+    /// Returns `true` if the placement cleared four lines or cleared lines
+    /// with a spin, continuing a back-to-back chain, `false` otherwise
+    pub fn difficult(&self) -> bool {
+        self.lines_cleared() >= 4 || (self.lines_cleared() > 0 && self.spin != Spin::None)
+    }
+
+    #[must_use]
+    /// Returns the combo after the placement, where 0 is the first of a run
+    /// of consecutive line clears, or `None` if the placement cleared no
+    /// lines
+    pub fn combo(&self) -> Option<u32> {
+        self.combo
+    }
+
+    #[must_use]
+    /// Returns the back-to-back chain after the placement, where 0 is the
+    /// first of a run of consecutive difficult clears, or `None` if the chain
+    /// is broken
+    pub fn back_to_back(&self) -> Option<u32> {
+        self.back_to_back
+    }
+
+    /// Records the combo and back-to-back chain after the placement
+    pub(crate) fn set_chains(&mut self, combo: Option<u32>, back_to_back: Option<u32>) {
+        self.combo = combo;
+        self.back_to_back = back_to_back;
+    }
+
+    #[must_use]
+    /// Returns the number of garbage lines the placement attacked with,
+    /// before cancelling incoming garbage
+    pub fn attack(&self) -> u32 {
+        self.attack
+    }
+
+    #[must_use]
+    /// Returns the number of the placement's attack lines released by
+    /// breaking a surge, which are sent as separate attacks
+    pub fn surge(&self) -> u32 {
+        self.surge
+    }
+
+    #[must_use]
+    /// Returns the number of garbage lines sent to the opponent, which is the
+    /// attack left over after cancelling incoming garbage
+    pub fn sent(&self) -> u32 {
+        self.sent
+    }
+
+    /// Records the number of garbage lines the placement attacked with, of
+    /// which `surge` were released by breaking a surge
+    pub(crate) fn set_attack(&mut self, attack: u32, surge: u32) {
+        self.attack = attack;
+        self.surge = surge;
+    }
+
+    /// Records the number of garbage lines sent to the opponent
+    pub(crate) fn set_sent(&mut self, sent: u32) {
+        self.sent = sent;
+    }
+}
@@ -1,65 +1,138 @@
+mod attack;
+mod combo;
+mod config;
 mod controller;
+mod garbage;
+mod gravity;
+mod lock;
+mod scoring;
+mod top_out;
+
+pub use attack::{AttackMultiplier, BackToBack};
+pub use config::GameConfig;
+pub use garbage::{Garbage, GarbageHole};
+pub use gravity::Gravity;
+pub use lock::LockReset;
+pub use top_out::GameEndReason;
 
 use crate::piece::DEFAULT_ORIENTATION;
-use crate::{Bag, Board, Controllable, Game, Piece, PieceType, TetrisError, TetrisInput};
+use crate::{
+    Board, Cell, Controllable, Game, InputHandler, KeyEvent, ParkMiller, Piece, PieceType,
+    PlacementResult, Position, Randomizer, SoftDropFactor, TetrisError, TetrisInput,
+};
+
+use rand::Rng;
 
 use std::collections::VecDeque;
 use std::fmt::{Display, Error, Formatter};
+use std::mem;
 
 pub struct TetrisGame {
     board: Board,
     hold_piece: Option<PieceType>,
     active_piece: Option<Piece>,
     piece_queue: VecDeque<PieceType>,
-    bag: Bag,
+    randomizer: Box<dyn Randomizer>,
+    input_handler: InputHandler,
 
-    game_over: bool,
+    config: GameConfig,
+    end_reason: Option<GameEndReason>,
+    last_placement: Option<PlacementResult>,
     can_hold: bool,
-    queue_size: usize,
+    soft_dropping: bool,
+    fall_progress: f64,
+    lock_timer: u32,
+    lock_resets: u32,
+    lowest_y: i32,
+    lines: u32,
+    score: u64,
+    back_to_back: Option<u32>,
+    combo: Option<u32>,
+    total_attack: u32,
+    surge_charge: u32,
+    incoming_garbage: VecDeque<Garbage>,
+    outgoing_attacks: Vec<u32>,
+    last_hole: Option<usize>,
+    garbage_rng: ParkMiller,
     frame: usize,
 }
 
 impl TetrisGame {
-    /// Creates a new sprint game with a specified bag and queue size
+    /// Creates a new sprint game with a specified bag and queue size, seeded
+    /// with `seed`, or randomly if `seed` is `None`
     #[must_use]
-    pub fn new(bag_size: u32, queue_size: usize) -> Self {
-        TetrisGame {
-            board: Board::new(),
-            hold_piece: None,
-            active_piece: None,
-            piece_queue: VecDeque::new(),
-            bag: Bag::new(bag_size),
-
-            game_over: false,
-            can_hold: true,
+    pub fn new(bag_size: u32, queue_size: usize, seed: Option<u64>) -> Self {
+        Self::with_config(GameConfig {
+            bag_size,
             queue_size,
-            frame: 0,
-        }
+            seed,
+            ..GameConfig::default()
+        })
+    }
+
+    /// Creates a new TETR.IO sprint game with a bag size of 7, queue size of 5
+    /// and SRS+ kicks, seeded with `seed`, or randomly if `seed` is `None`.
+    /// Games with the same seed have the same piece queue as TETR.IO.
+    #[must_use]
+    pub fn new_tetrio(seed: Option<u64>) -> Self {
+        Self::with_config(GameConfig {
+            seed,
+            ..GameConfig::tetrio()
+        })
+    }
+
+    /// Creates a new game played with the rules in `config`. If `config` has
+    /// no seed, the game is seeded randomly and its seed is recorded in its
+    /// configuration.
+    #[must_use]
+    pub fn with_config(mut config: GameConfig) -> Self {
+        let seed = *config.seed.get_or_insert_with(|| rand::rng().random());
+        let randomizer = config.randomizer.build(config.bag_size, seed);
+        Self::with_randomizer(config, randomizer)
     }
 
-    /// Creates a new TETR.IO sprint game with a bag size of 7 and queue size of
-    /// 5
+    /// Creates a new game played with the rules in `config`, drawing its
+    /// pieces from `randomizer` instead of `GameConfig::randomizer`
     #[must_use]
-    pub fn new_tetrio() -> Self {
+    pub fn with_randomizer(mut config: GameConfig, randomizer: Box<dyn Randomizer>) -> Self {
+        let seed = *config.seed.get_or_insert_with(|| rand::rng().random());
+
         TetrisGame {
             board: Board::new(),
             hold_piece: None,
             active_piece: None,
             piece_queue: VecDeque::new(),
-            bag: Bag::new(7),
+            randomizer,
+            input_handler: InputHandler::new(config.handling),
 
-            game_over: false,
+            lock_timer: config.lock_delay,
+            config,
+            end_reason: None,
+            last_placement: None,
             can_hold: true,
-            queue_size: 5,
+            soft_dropping: false,
+            fall_progress: 0.0,
+            lock_resets: 0,
+            lowest_y: 0,
+            lines: 0,
+            score: 0,
+            back_to_back: None,
+            combo: None,
+            total_attack: 0,
+            surge_charge: 0,
+            incoming_garbage: VecDeque::new(),
+            outgoing_attacks: Vec::new(),
+            last_hole: None,
+            garbage_rng: ParkMiller::new(seed),
             frame: 0,
         }
     }
 
-    /// Fills the queue by drawing pieces from the game's bag until
-    /// `self.piece_queue.len() == self.queue_size`
+    /// Fills the queue by drawing pieces from the game's randomizer until
+    /// `self.piece_queue.len() == self.config.queue_size`
     pub fn fill_queue(&mut self) {
-        while self.piece_queue.len() < self.queue_size {
-            self.piece_queue.push_back(self.bag.draw());
+        while self.piece_queue.len() < self.config.queue_size {
+            self.piece_queue.push_back(self.randomizer.draw());
         }
     }
 
@@ -88,10 +161,46 @@ impl TetrisGame {
         &self.piece_queue
     }
 
-    /// Returns a reference to the upcoming piece queue
+    /// Advances the game by one frame like `Game::next_frame`, first turning
+    /// the key events of the frame into inputs using the game's handling
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `Game::next_frame`
+    pub fn next_frame_with_keys(&mut self, events: &[KeyEvent]) -> Result<(), TetrisError> {
+        let mut inputs = self.input_handler.frame(events);
+        self.next_frame(&mut inputs)
+    }
+
+    /// Returns the result of the most recently locked piece, or `None` if no
+    /// piece has been locked yet
+    #[must_use]
+    pub fn last_placement(&self) -> Option<&PlacementResult> {
+        self.last_placement.as_ref()
+    }
+
+    /// Returns the total number of lines cleared
+    #[must_use]
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    /// Returns the current level, which increases every 10 lines cleared
+    #[must_use]
+    pub fn level(&self) -> u32 {
+        self.config.start_level + self.lines / 10
+    }
+
+    /// Returns a reference to the rules the game is played with
+    #[must_use]
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Returns `true` if the game has ended, `false` otherwise
     #[must_use]
     pub fn game_over(&self) -> bool {
-        self.game_over
+        self.end_reason.is_some()
     }
 
     fn load_next_piece(&mut self) -> Result<(), TetrisError> {
@@ -99,15 +208,125 @@ impl TetrisGame {
             return Err(TetrisError::FailedToLoadPiece);
         }
 
-        self.active_piece = Some(Piece::new(self.piece_queue.pop_front().unwrap()));
+        let piece = Piece::new(self.piece_queue.pop_front().unwrap());
         self.fill_queue();
+        self.spawn(piece);
         Ok(())
     }
+
+    /// Makes `piece` the active piece, ending the game with a block out if it
+    /// overlaps the stack
+    fn spawn(&mut self, piece: Piece) {
+        if piece.collides(&self.board) {
+            self.end(GameEndReason::BlockOut);
+        }
+
+        self.active_piece = Some(piece);
+        self.fall_progress = 0.0;
+        self.reset_lock_delay();
+    }
+
+    /// Places the active piece on the board, hard dropping it first if
+    /// `hard_drop` is `true`, then loads the next piece unless the piece
+    /// locked out or incoming garbage topped out
+    fn lock_active_piece(&mut self, hard_drop: bool) -> Result<(), TetrisError> {
+        let piece = self
+            .active_piece
+            .take()
+            .ok_or(TetrisError::GameNotStarted)?;
+
+        let start_y = piece.y();
+        let mut placement = if hard_drop {
+            self.board.hard_drop(piece, self.config.spin_rule)?
+        } else {
+            self.board.lock(&piece, self.config.spin_rule)?
+        };
+        let lock_out = self.lock_out(placement.cells());
+
+        if hard_drop {
+            let distance = placement.position().y() - start_y;
+            self.score_drop(u32::try_from(distance).unwrap_or(0), true);
+        }
+        self.update_chains(&mut placement);
+        self.update_attack(&mut placement);
+        self.update_garbage(&mut placement);
+        self.score_placement(&placement);
+        self.lines += u32::try_from(placement.lines_cleared()).unwrap_or(u32::MAX);
+        self.can_hold = true;
+        self.last_placement = Some(placement);
+
+        if let Some(reason) = lock_out {
+            self.end(reason);
+        }
+        if self.game_over() {
+            return Ok(());
+        }
+        self.load_next_piece()
+    }
+
+    /// Moves the active piece down according to the gravity, or the soft
+    /// drop speed if soft dropping this frame, stopping at the first
+    /// obstruction
+    fn fall(&mut self) {
+        let gravity = self
+            .config
+            .gravity
+            .cells_per_frame(self.level(), self.frame);
+        let soft_dropping = mem::take(&mut self.soft_dropping);
+
+        let Some(piece) = self.active_piece.as_mut() else {
+            return;
+        };
+
+        self.fall_progress += match (soft_dropping, self.config.handling.sdf) {
+            (false, _) if gravity >= 20.0 => f64::INFINITY,
+            (false, _) => gravity,
+            (true, SoftDropFactor::Factor(factor)) => gravity * factor,
+            (true, SoftDropFactor::Infinite) => f64::INFINITY,
+        };
+
+        let mut fallen = 0;
+        while self.fall_progress >= 1.0 {
+            if piece.move_down(&self.board).is_err() {
+                self.fall_progress = 0.0;
+                break;
+            }
+            self.fall_progress -= 1.0;
+            fallen += 1;
+        }
+
+        if soft_dropping {
+            self.score_drop(fallen, false);
+        }
+    }
+}
+
+#[cfg(test)]
+impl TetrisGame {
+    /// Fills columns 0 to 8 of every row from `top_row` down, leaving a well
+    /// in the rightmost column
+    pub(crate) fn fill_well(&mut self, top_row: usize) {
+        for y in top_row..24 {
+            for x in 0..9 {
+                self.board.fill_cell(x, y);
+            }
+        }
+    }
+
+    /// Replaces the active piece with a vertical I piece and hard drops it
+    /// into the well in the rightmost column
+    pub(crate) fn drop_i_in_well(&mut self) {
+        let mut piece = Piece::new(PieceType::I);
+        piece.rotate_cw(&self.board, crate::KickTable::Srs).unwrap();
+        while piece.move_right(&self.board).is_ok() {}
+        self.active_piece = Some(piece);
+        self.hard_drop().unwrap();
+    }
 }
 
 impl Default for TetrisGame {
     fn default() -> Self {
-        Self::new(7, 5)
+        Self::new(7, 5, None)
     }
 }
 
@@ -125,11 +344,11 @@ impl Display for TetrisGame {
                 board_display[row][col + 1] = char;
             }
         }
-        // Overlay active piece
+        // Overlay active piece, with the same glyph as its locked cells
         if let Some(piece) = &self.active_piece {
-            let pos_mask = piece.get_pos_mask();
-            for pos in pos_mask {
-                board_display[pos.y() as usize][pos.x() as usize + 1] = '■';
+            let glyph = Cell::Piece(piece.kind()).glyph();
+            for (x, y) in piece.get_pos_mask().iter().filter_map(Position::indices) {
+                board_display[y][x + 1] = glyph;
             }
         }
 
@@ -201,8 +420,10 @@ impl Display for TetrisGame {
             writeln!(f, "")?;
         }
         writeln!(f, "       +----------+       ")?;
+        // Wide enough for any score, so the line is always as wide as the rest
+        writeln!(f, "Score {:>20}", self.score)?;
 
-        if self.game_over {
+        if self.game_over() {
             writeln!(f, "        GAME  OVER        ")?;
         }
 
@@ -212,7 +433,7 @@ impl Display for TetrisGame {
 
 impl Game for TetrisGame {
     fn start(&mut self) -> Result<(), TetrisError> {
-        if self.game_over {
+        if self.game_over() {
             return Err(TetrisError::GameOver);
         }
 
@@ -221,22 +442,59 @@ impl Game for TetrisGame {
     }
 
     fn next_frame(&mut self, inputs: &mut VecDeque<TetrisInput>) -> Result<(), TetrisError> {
-        if self.game_over {
+        if self.game_over() {
             return Err(TetrisError::GameOver);
         }
         if self.active_piece().is_none() {
             return Err(TetrisError::GameNotStarted);
         }
 
-        while !inputs.is_empty() {
-            let _ = self.execute_input(inputs.pop_front().unwrap());
+        while let Some(input) = inputs.pop_front() {
+            let _ = self.execute_input(input);
+
+            if self.game_over() {
+                return Ok(());
+            }
         }
+        self.fall();
+        self.update_lock_delay()?;
 
         self.frame += 1;
         Ok(())
     }
 
     fn end_game(&mut self) {
-        self.game_over = true;
+        self.end(GameEndReason::Forfeit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Game, TetrisGame};
+
+    #[test]
+    fn display_rows_have_equal_width() {
+        let mut game = TetrisGame::new_tetrio(Some(0));
+        game.start().unwrap();
+
+        let kind = game.active_piece().unwrap().kind().to_string();
+        assert!(
+            game.to_string()
+                .lines()
+                .take(4)
+                .any(|line| line.contains(&kind)),
+            "The active piece should be displayed as its piece letter"
+        );
+
+        for score in [0, 1_000_000, u64::MAX] {
+            game.score = score;
+            assert!(
+                game.to_string()
+                    .lines()
+                    .all(|line| line.chars().count() == 26),
+                "Every line of the display should be as wide as the board rows with a score of \
+                {score}"
+            );
+        }
     }
 }
@@ -0,0 +1,400 @@
+use crate::{PlacementResult, Spin, TetrisGame};
+
+/// The attack added to every back-to-back clear before chaining
+const BACK_TO_BACK_BONUS: f64 = 1.0;
+/// How quickly the back-to-back bonus grows with the length of the chain
+const BACK_TO_BACK_BONUS_LOG: f64 = 0.8;
+/// The fraction of attack added per combo
+const COMBO_BONUS: f64 = 0.25;
+/// The multiplier of the minimum attack of a combo, which lets combos of
+/// singles send garbage
+const COMBO_MINIMUM_MULTIPLIER: f64 = 5.0;
+/// The attack added by a perfect clear
+const PERFECT_CLEAR_ATTACK: f64 = 10.0;
+/// The back-to-back chain at which surge starts charging
+const SURGE_START: u32 = 4;
+/// The number of attacks a released surge is split into
+const SURGE_SEGMENTS: u32 = 3;
+
+/// How back-to-back chains add to attack
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum BackToBack {
+    /// Every clear of a chain adds a bonus that grows logarithmically with
+    /// the length of the chain
+    #[default]
+    Chaining,
+    /// Every clear of a chain adds a flat bonus, while chains of at least 4
+    /// charge a surge, which is released as a spike of attacks when the chain
+    /// is broken, as in TETR.IO's current ruleset
+    Surge,
+}
+
+/// Scales attack over the course of a game, as in TETR.IO's margin time
+/// settings
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AttackMultiplier {
+    /// The multiplier at the start of the game
+    pub base: f64,
+    /// How much the multiplier increases every frame once `margin` frames
+    /// have passed
+    pub increase: f64,
+    /// The number of frames before the multiplier starts increasing
+    pub margin: usize,
+}
+
+impl AttackMultiplier {
+    /// TETR.IO's margin time settings, increasing the multiplier by 0.008
+    /// every second after 3 minutes
+    pub const TETRIO: AttackMultiplier = AttackMultiplier {
+        base: 1.0,
+        increase: 0.008 / 60.0,
+        margin: 180 * 60,
+    };
+
+    #[must_use]
+    /// Returns the multiplier on frame `frame`
+    pub fn at_frame(&self, frame: usize) -> f64 {
+        let frames_increased = u32::try_from(frame.saturating_sub(self.margin)).unwrap_or(u32::MAX);
+        self.base + self.increase * f64::from(frames_increased)
+    }
+}
+
+impl Default for AttackMultiplier {
+    /// Creates a multiplier that stays at 1 for the whole game
+    fn default() -> Self {
+        AttackMultiplier {
+            base: 1.0,
+            increase: 0.0,
+            margin: 0,
+        }
+    }
+}
+
+/// Returns TETR.IO's base attack for clearing `lines` with `spin`. Minis send
+/// the same as clears without a spin, but continue back-to-back chains
+fn base_attack(lines: usize, spin: Spin) -> f64 {
+    let attack: [f64; 5] = match spin {
+        Spin::None | Spin::Mini => [0.0, 0.0, 1.0, 2.0, 4.0],
+        Spin::Full => [0.0, 2.0, 4.0, 6.0, 10.0],
+    };
+    attack[lines.min(4)]
+}
+
+/// Returns the attack added by a back-to-back chain of `chain`, which grows
+/// logarithmically with the length of the chain
+fn back_to_back_attack(chain: u32) -> f64 {
+    let log = (f64::from(chain) * BACK_TO_BACK_BONUS_LOG).ln_1p();
+    let chaining = if chain == 1 {
+        0.0
+    } else {
+        (1.0 + log % 1.0) / 3.0
+    };
+
+    BACK_TO_BACK_BONUS * ((1.0 + log).floor() + chaining)
+}
+
+/// Returns the number of garbage lines sent by clearing `lines` with `spin`,
+/// using TETR.IO's attack table scaled by `multiplier`. `combo` and
+/// `back_to_back` are the combo and back-to-back chain after the clear.
+fn attack(
+    lines: usize,
+    spin: Spin,
+    combo: Option<u32>,
+    back_to_back: Option<u32>,
+    perfect_clear: bool,
+    multiplier: f64,
+) -> u32 {
+    if lines == 0 {
+        return 0;
+    }
+
+    let mut attack = base_attack(lines, spin);
+
+    if let Some(chain @ 1..) = back_to_back {
+        attack += back_to_back_attack(chain);
+    }
+
+    if let Some(combo @ 1..) = combo {
+        let combo = f64::from(combo);
+        attack *= 1.0 + COMBO_BONUS * combo;
+
+        if combo > 1.0 {
+            attack = attack.max((COMBO_BONUS * COMBO_MINIMUM_MULTIPLIER * combo).ln_1p());
+        }
+    }
+
+    if perfect_clear {
+        lines_sent(attack * multiplier) + lines_sent(PERFECT_CLEAR_ATTACK * multiplier)
+    } else {
+        lines_sent(attack * multiplier)
+    }
+}
+
+/// Returns `surge` lines split as evenly as possible into separate attacks,
+/// largest first, leaving out empty attacks
+pub(super) fn surge_segments(surge: u32) -> impl Iterator<Item = u32> {
+    (0..SURGE_SEGMENTS)
+        .map(move |segment| surge / SURGE_SEGMENTS + u32::from(segment < surge % SURGE_SEGMENTS))
+        .filter(|&lines| lines > 0)
+}
+
+/// Returns the whole number of garbage lines sent by `attack`
+fn lines_sent(attack: f64) -> u32 {
+    // Attack is always a small non-negative number, so truncation is safe
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let lines = attack.floor() as u32;
+    lines
+}
+
+impl TetrisGame {
+    /// Returns the total number of garbage lines sent
+    #[must_use]
+    pub fn total_attack(&self) -> u32 {
+        self.total_attack
+    }
+
+    /// Returns the surge charged by the current back-to-back chain, which is
+    /// released when the chain is broken. Always 0 unless
+    /// `GameConfig::back_to_back` is `BackToBack::Surge`.
+    #[must_use]
+    pub fn surge_charge(&self) -> u32 {
+        self.surge_charge
+    }
+
+    /// Calculates the attack of `placement`, scaled by the attack multiplier
+    /// of the current frame, and records it in it. In surge mode, also
+    /// charges the surge, or releases it if `placement` broke the chain.
+    pub(super) fn update_attack(&mut self, placement: &mut PlacementResult) {
+        let multiplier = self.config.attack_multiplier.at_frame(self.frame);
+        let back_to_back = match self.config.back_to_back {
+            BackToBack::Chaining => placement.back_to_back(),
+            // A chain of 1 adds the flat bonus without growing
+            BackToBack::Surge => placement.back_to_back().map(|chain| chain.min(1)),
+        };
+
+        let mut surge = 0;
+        if self.config.back_to_back == BackToBack::Surge {
+            match placement.back_to_back() {
+                Some(chain @ SURGE_START..) => self.surge_charge = chain,
+                None if placement.lines_cleared() > 0 => {
+                    surge = lines_sent(f64::from(self.surge_charge) * multiplier);
+                    self.surge_charge = 0;
+                }
+                _ => {}
+            }
+        }
+
+        let lines_sent = attack(
+            placement.lines_cleared(),
+            placement.spin(),
+            placement.combo(),
+            back_to_back,
+            placement.perfect_clear(),
+            multiplier,
+        ) + surge;
+
+        placement.set_attack(lines_sent, surge);
+        self.total_attack += lines_sent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameConfig};
+
+    #[test]
+    fn base_attacks() {
+        let expected = [
+            (1, Spin::None, 0),
+            (2, Spin::None, 1),
+            (3, Spin::None, 2),
+            (4, Spin::None, 4),
+            (1, Spin::Mini, 0),
+            (2, Spin::Mini, 1),
+            (1, Spin::Full, 2),
+            (2, Spin::Full, 4),
+            (3, Spin::Full, 6),
+        ];
+
+        for (lines, spin, lines_sent) in expected {
+            assert_eq!(
+                lines_sent,
+                attack(lines, spin, Some(0), None, false, 1.0),
+                "Incorrect attack for clearing {lines} lines with {spin:?} spin"
+            );
+        }
+    }
+
+    #[test]
+    fn back_to_back_attacks() {
+        assert_eq!(
+            5,
+            attack(2, Spin::Full, Some(0), Some(1), false, 1.0),
+            "A back-to-back T-spin double should send 5 lines"
+        );
+        assert_eq!(
+            5,
+            attack(4, Spin::None, Some(0), Some(1), false, 1.0),
+            "A back-to-back quad should send 5 lines"
+        );
+        assert_eq!(
+            4,
+            attack(2, Spin::Full, Some(0), Some(0), false, 1.0),
+            "The first clear of a back-to-back chain should not get a bonus"
+        );
+
+        let bonuses = [(1, 1.0), (2, 1.652), (3, 2.408), (8, 3.334)];
+        for (chain, bonus) in bonuses {
+            assert!(
+                (back_to_back_attack(chain) - bonus).abs() < 0.001,
+                "Incorrect back-to-back bonus for a chain of {chain}. Expected {bonus}, was {}",
+                back_to_back_attack(chain)
+            );
+        }
+    }
+
+    #[test]
+    fn combo_attacks() {
+        assert_eq!(
+            1,
+            attack(2, Spin::None, Some(2), None, false, 1.0),
+            "A double at combo 2 should send floor(1 * 1.5) lines"
+        );
+        assert_eq!(
+            6,
+            attack(2, Spin::Full, Some(2), None, false, 1.0),
+            "A T-spin double at combo 2 should send floor(4 * 1.5) lines"
+        );
+        assert_eq!(
+            0,
+            attack(1, Spin::None, Some(1), None, false, 1.0),
+            "A single at combo 1 should send no lines"
+        );
+        assert_eq!(
+            1,
+            attack(1, Spin::None, Some(4), None, false, 1.0),
+            "A single at combo 4 should send the minimum combo attack, floor(ln(1 + 5))"
+        );
+    }
+
+    #[test]
+    fn perfect_clear_attack() {
+        assert_eq!(14, attack(4, Spin::None, Some(0), None, true, 1.0));
+        assert_eq!(10, attack(1, Spin::None, Some(0), None, true, 1.0));
+    }
+
+    #[test]
+    fn no_lines_no_attack() {
+        assert_eq!(0, attack(0, Spin::Full, None, Some(3), false, 1.0));
+    }
+
+    #[test]
+    fn placements_record_attack() {
+        let mut game = TetrisGame::new(7, 5, None);
+        game.start().unwrap();
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(4, game.last_placement().unwrap().attack());
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(
+            6,
+            game.last_placement().unwrap().attack(),
+            "A back-to-back quad at combo 1 should send floor(5 * 1.25) lines"
+        );
+        assert_eq!(10, game.total_attack());
+    }
+
+    #[test]
+    fn attack_multiplier() {
+        let multiplier = AttackMultiplier {
+            base: 1.0,
+            increase: 0.25,
+            margin: 60,
+        };
+        assert!((multiplier.at_frame(60) - 1.0).abs() < f64::EPSILON);
+        assert!((multiplier.at_frame(62) - 1.5).abs() < f64::EPSILON);
+
+        assert_eq!(
+            6,
+            attack(2, Spin::Full, Some(0), None, false, 1.5),
+            "A T-spin double should send floor(4 * 1.5) lines with a 1.5x multiplier"
+        );
+        assert_eq!(
+            15,
+            attack(1, Spin::None, Some(0), None, true, 1.5),
+            "The perfect clear bonus should be multiplied"
+        );
+
+        let tetrio = AttackMultiplier::TETRIO;
+        assert!((tetrio.at_frame(180 * 60) - 1.0).abs() < f64::EPSILON);
+        assert!((tetrio.at_frame(181 * 60) - 1.008).abs() < 1e-9);
+    }
+
+    #[test]
+    fn surge_segments_are_even() {
+        assert_eq!(vec![2, 1, 1], surge_segments(4).collect::<Vec<_>>());
+        assert_eq!(vec![3, 2, 2], surge_segments(7).collect::<Vec<_>>());
+        assert_eq!(vec![1], surge_segments(1).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn surge_charges_and_releases() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            back_to_back: BackToBack::Surge,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+
+        for chain in 0..5 {
+            game.fill_well(19);
+            game.drop_i_in_well();
+            assert_eq!(
+                if chain < 4 { 0 } else { chain },
+                game.surge_charge(),
+                "Surge should only charge once the back-to-back chain reaches 4"
+            );
+        }
+        assert_eq!(
+            10,
+            game.last_placement().unwrap().attack(),
+            "A fifth quad in surge mode should send (4 + 1) * 2 lines, with a flat back-to-back \
+            bonus"
+        );
+
+        game.take_outgoing_attacks();
+        game.drop_i_in_well();
+        let placement = game.last_placement().unwrap();
+        assert_eq!((5, 4), (placement.attack(), placement.surge()));
+        assert_eq!(0, game.surge_charge());
+        assert_eq!(
+            vec![1, 2, 1, 1],
+            game.take_outgoing_attacks(),
+            "A released surge should be sent as separate attacks after the clear's own attack"
+        );
+    }
+
+    #[test]
+    fn surge_cancels_garbage() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            back_to_back: BackToBack::Surge,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+
+        for _ in 0..5 {
+            game.fill_well(19);
+            game.drop_i_in_well();
+        }
+        game.take_outgoing_attacks();
+
+        game.receive_garbage(3);
+        game.drop_i_in_well();
+        assert_eq!(vec![1, 1], game.take_outgoing_attacks());
+        assert_eq!(2, game.last_placement().unwrap().sent());
+        assert_eq!(0, game.pending_garbage());
+    }
+}
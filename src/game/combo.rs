@@ -0,0 +1,104 @@
+use crate::{PlacementResult, TetrisGame};
+
+impl TetrisGame {
+    /// Returns the current combo, where 0 is the first of a run of
+    /// consecutive line clears, or `None` if the last placement cleared no
+    /// lines
+    #[must_use]
+    pub fn combo(&self) -> Option<u32> {
+        self.combo
+    }
+
+    /// Returns the current back-to-back chain, where 0 is the first of a run
+    /// of consecutive difficult clears, or `None` if there is no chain
+    #[must_use]
+    pub fn back_to_back(&self) -> Option<u32> {
+        self.back_to_back
+    }
+
+    /// Updates the combo and back-to-back chain for `placement` and records
+    /// them in it. Placements that clear no lines reset the combo but leave
+    /// the back-to-back chain untouched.
+    pub(super) fn update_chains(&mut self, placement: &mut PlacementResult) {
+        if placement.lines_cleared() == 0 {
+            self.combo = None;
+        } else {
+            self.combo = Some(self.combo.map_or(0, |combo| combo + 1));
+            self.back_to_back = if placement.difficult() {
+                Some(self.back_to_back.map_or(0, |chain| chain + 1))
+            } else {
+                None
+            };
+        }
+
+        placement.set_chains(self.combo, self.back_to_back);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Controllable, Game, TetrisGame};
+
+    /// Returns the combo and back-to-back chain of the game and of its last
+    /// placement
+    fn chains(game: &TetrisGame) -> [(Option<u32>, Option<u32>); 2] {
+        let placement = game.last_placement().unwrap();
+        [
+            (game.combo(), game.back_to_back()),
+            (placement.combo(), placement.back_to_back()),
+        ]
+    }
+
+    #[test]
+    fn combo_and_back_to_back() {
+        let mut game = TetrisGame::new(7, 5, None);
+        game.start().unwrap();
+        assert_eq!((None, None), (game.combo(), game.back_to_back()));
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(
+            [(Some(0), Some(0)); 2],
+            chains(&game),
+            "A first tetris should start both the combo and the back-to-back chain"
+        );
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(
+            [(Some(1), Some(1)); 2],
+            chains(&game),
+            "A second tetris should continue both the combo and the back-to-back chain"
+        );
+
+        game.drop_i_in_well();
+        assert_eq!(
+            [(Some(2), None); 2],
+            chains(&game),
+            "A single should continue the combo but break the back-to-back chain"
+        );
+
+        game.hard_drop().unwrap();
+        assert_eq!(
+            [(None, None); 2],
+            chains(&game),
+            "A placement that clears no lines should reset the combo"
+        );
+    }
+
+    #[test]
+    fn no_clear_keeps_back_to_back() {
+        let mut game = TetrisGame::new(7, 5, None);
+        game.start().unwrap();
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        game.hard_drop().unwrap();
+
+        assert_eq!(
+            [(None, Some(0)); 2],
+            chains(&game),
+            "A placement that clears no lines should not break the back-to-back chain"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use super::{AttackMultiplier, BackToBack, GarbageHole, Gravity, LockReset};
+use crate::piece::{KickTable, SpinRule};
+use crate::{Handling, RandomizerKind};
+
+/// The rules a `TetrisGame` is played with
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    /// The randomizer that generates the piece queue
+    pub randomizer: RandomizerKind,
+    /// The number of pieces in each bag of `RandomizerKind::Bag`
+    pub bag_size: u32,
+    /// The number of upcoming pieces shown in the piece queue
+    pub queue_size: usize,
+    /// The kicks tested when rotating the active piece
+    pub kick_table: KickTable,
+    /// The speed the active piece falls at
+    pub gravity: Gravity,
+    /// The level the game starts at, which increases every 10 lines cleared
+    pub start_level: u32,
+    /// How held keys are turned into movement, including the soft drop factor
+    pub handling: Handling,
+    /// The number of frames the active piece can rest on the stack before
+    /// locking
+    pub lock_delay: u32,
+    /// When moving or rotating the active piece resets its lock delay
+    pub lock_reset: LockReset,
+    /// Which pieces can spin, and whether their spins are minis or full spins
+    pub spin_rule: SpinRule,
+    /// Whether locking a piece partially above the visible 20 rows ends the
+    /// game
+    pub partial_lock_out: bool,
+    /// The most incoming garbage lines inserted into the board per placement
+    pub garbage_cap: u32,
+    /// How the hole column of incoming garbage is chosen
+    pub garbage_hole: GarbageHole,
+    /// The number of frames incoming garbage waits before it can be inserted
+    /// into the board
+    pub garbage_delay: usize,
+    /// Scales outgoing attack over the course of the game
+    pub attack_multiplier: AttackMultiplier,
+    /// How back-to-back chains add to attack
+    pub back_to_back: BackToBack,
+    /// The seed of the game's random number generator, or `None` to seed it
+    /// randomly. Games with the same seed draw identical pieces and
+    /// receive identical garbage.
+    pub seed: Option<u64>,
+}
+
+impl GameConfig {
+    /// Creates a TETR.IO configuration with a 7-bag randomizer, queue size of
+    /// 5, SRS+ kicks, a gravity of 0.02 cells per frame, a 30 frame lock delay
+    /// with up to 15 move resets, all-mini spins, a garbage cap of 8, a 20
+    /// frame garbage delay, TETR.IO's margin time, chaining back-to-back and
+    /// default handling
+    #[must_use]
+    pub fn tetrio() -> Self {
+        GameConfig {
+            randomizer: RandomizerKind::Bag,
+            bag_size: 7,
+            queue_size: 5,
+            kick_table: KickTable::SrsPlus,
+            gravity: Gravity::Tetrio {
+                gravity: 0.02,
+                increase: 0.0,
+                margin: 0,
+            },
+            start_level: 1,
+            handling: Handling::tetrio(),
+            lock_delay: 30,
+            lock_reset: LockReset::Move { limit: 15 },
+            spin_rule: SpinRule::AllMini,
+            partial_lock_out: false,
+            garbage_cap: 8,
+            garbage_hole: GarbageHole::CLEAN,
+            garbage_delay: 20,
+            attack_multiplier: AttackMultiplier::TETRIO,
+            back_to_back: BackToBack::Chaining,
+            seed: None,
+        }
+    }
+}
+
+impl Default for GameConfig {
+    /// Creates a guideline configuration with a 7-bag randomizer, queue size
+    /// of 5, SRS kicks, guideline marathon gravity starting at level 1, a 30
+    /// frame lock delay with up to 15 move resets, T-spins only, chaining
+    /// back-to-back, a garbage cap of 8 with no garbage delay and guideline
+    /// handling
+    fn default() -> Self {
+        GameConfig {
+            randomizer: RandomizerKind::Bag,
+            bag_size: 7,
+            queue_size: 5,
+            kick_table: KickTable::Srs,
+            gravity: Gravity::Guideline,
+            start_level: 1,
+            handling: Handling::default(),
+            lock_delay: 30,
+            lock_reset: LockReset::Move { limit: 15 },
+            spin_rule: SpinRule::TSpinsOnly,
+            partial_lock_out: false,
+            garbage_cap: 8,
+            garbage_hole: GarbageHole::CLEAN,
+            garbage_delay: 0,
+            attack_multiplier: AttackMultiplier::default(),
+            back_to_back: BackToBack::Chaining,
+            seed: None,
+        }
+    }
+}
@@ -1,42 +1,49 @@
 use crate::Controllable;
-use crate::{Game, TetrisError, TetrisGame};
-
-use std::mem;
+use crate::{Game, Piece, TetrisError, TetrisGame};
 
 use tracing::{Level, event};
 
 impl Controllable for TetrisGame {
     fn move_left(&mut self) -> Result<(), TetrisError> {
-        self.active_piece.as_mut().unwrap().move_left(&self.board)
+        self.manipulate(Piece::move_left)
     }
 
     fn move_right(&mut self) -> Result<(), TetrisError> {
-        self.active_piece.as_mut().unwrap().move_right(&self.board)
+        self.manipulate(Piece::move_right)
+    }
+
+    fn snap_left(&mut self) -> Result<(), TetrisError> {
+        self.manipulate(Piece::snap_left)
+    }
+
+    fn snap_right(&mut self) -> Result<(), TetrisError> {
+        self.manipulate(Piece::snap_right)
     }
 
     fn rotate_cw(&mut self) -> Result<(), TetrisError> {
-        self.active_piece.as_mut().unwrap().rotate_cw()
+        let kick_table = self.config.kick_table;
+        self.manipulate(|piece, board| piece.rotate_cw(board, kick_table))
     }
 
     fn rotate_ccw(&mut self) -> Result<(), TetrisError> {
-        self.active_piece.as_mut().unwrap().rotate_ccw()
+        let kick_table = self.config.kick_table;
+        self.manipulate(|piece, board| piece.rotate_ccw(board, kick_table))
     }
 
     fn rotate_180(&mut self) -> Result<(), TetrisError> {
-        self.active_piece.as_mut().unwrap().rotate_180()
+        let kick_table = self.config.kick_table;
+        self.manipulate(|piece, board| piece.rotate_180(board, kick_table))
+    }
+
+    fn soft_drop(&mut self) -> Result<(), TetrisError> {
+        self.soft_dropping = true;
+        Ok(())
     }
 
     fn hard_drop(&mut self) -> Result<(), TetrisError> {
         event!(Level::INFO, "Hard drop initiated");
 
-        let mut piece_to_drop = None;
-        mem::swap(&mut self.active_piece, &mut piece_to_drop);
-
-        self.board.hard_drop(piece_to_drop.unwrap())?;
-        self.can_hold = true;
-        self.load_next_piece().unwrap();
-
-        Ok(())
+        self.lock_active_piece(true)
     }
 
     fn hold(&mut self) -> Result<(), TetrisError> {
@@ -44,17 +51,17 @@ impl Controllable for TetrisGame {
             return Err(TetrisError::InvalidHold);
         }
 
-        let mut prev_hold_piece = match self.hold_piece {
+        let prev_hold_piece = match self.hold_piece {
             Some(piece_type) => Some(piece_type.into()),
             None => None,
         };
-        mem::swap(&mut self.active_piece, &mut prev_hold_piece);
+        let prev_active_piece = self.active_piece.take();
 
-        if self.active_piece().is_none() {
-            self.load_next_piece().unwrap();
+        match prev_hold_piece {
+            Some(piece) => self.spawn(piece),
+            None => self.load_next_piece().unwrap(),
         }
 
-        let prev_active_piece = prev_hold_piece;
         self.hold_piece = match prev_active_piece {
             Some(piece) => Some(piece.into()),
             None => None,
@@ -68,10 +75,13 @@ impl Controllable for TetrisGame {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{GameConfig, Gravity, Handling, SoftDropFactor, TetrisInput};
+
+    use std::collections::VecDeque;
 
     #[test]
     fn successful_hold() {
-        let mut game = TetrisGame::new_tetrio();
+        let mut game = TetrisGame::new_tetrio(None);
         game.start().unwrap();
 
         let active_kind = game
@@ -120,7 +130,7 @@ mod tests {
 
     #[test]
     fn unsuccessful_hold() {
-        let mut game = TetrisGame::new_tetrio();
+        let mut game = TetrisGame::new_tetrio(None);
         game.start().unwrap();
 
         assert_eq!(
@@ -156,4 +166,78 @@ mod tests {
             "Third hold after a piece placement without placing another piece should be invalid."
         );
     }
+
+    #[test]
+    fn soft_drop_with_factor() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            gravity: Gravity::Fixed(0.25),
+            handling: Handling {
+                sdf: SoftDropFactor::Factor(2.0),
+                ..Handling::default()
+            },
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        let start_y = game.active_piece().unwrap().y();
+
+        for frame in 1..=8 {
+            game.next_frame(&mut VecDeque::from([TetrisInput::SoftDrop]))
+                .unwrap();
+            assert_eq!(
+                start_y + frame / 2,
+                game.active_piece().unwrap().y(),
+                "Soft dropping at half a cell per frame should move the piece \
+                one cell every two frames, but it was at an incorrect y-position \
+                after {frame} frames"
+            );
+        }
+
+        game.next_frame(&mut VecDeque::new()).unwrap();
+        assert_eq!(
+            start_y + 4,
+            game.active_piece().unwrap().y(),
+            "Active piece should not soft drop on frames without a soft drop input"
+        );
+    }
+
+    #[test]
+    fn soft_drop_infinite() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            handling: Handling {
+                sdf: SoftDropFactor::Infinite,
+                ..Handling::default()
+            },
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+
+        game.next_frame(&mut VecDeque::from([TetrisInput::SoftDrop]))
+            .unwrap();
+        let mut piece = game.active_piece().unwrap().clone();
+        assert_eq!(
+            Err(TetrisError::InvalidDownMove),
+            piece.move_down(game.board()),
+            "Infinite soft drop should move the active piece as low as it can go in one frame"
+        );
+    }
+
+    #[test]
+    fn hard_drop_reports_placement() {
+        let mut game = TetrisGame::new_tetrio(None);
+        game.start().unwrap();
+        assert!(game.last_placement().is_none());
+
+        let active_kind = game.active_piece().unwrap().kind();
+        game.hard_drop().unwrap();
+
+        let placement = game
+            .last_placement()
+            .expect("Last placement should not be None after a hard drop.");
+        assert_eq!(
+            active_kind,
+            placement.kind(),
+            "Last placement should be of the piece that was hard dropped."
+        );
+        assert!(placement.cleared_rows().is_empty());
+    }
 }
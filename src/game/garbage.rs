@@ -0,0 +1,464 @@
+use super::GameEndReason;
+use super::attack::surge_segments;
+use crate::{PlacementResult, TetrisGame};
+
+use std::collections::VecDeque;
+use std::{iter, mem};
+
+/// How the hole column of incoming garbage is chosen
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GarbageHole {
+    /// Holes are chosen randomly, as in TETR.IO's garbage messiness settings.
+    /// Each attack moves the hole to a different column with probability
+    /// `change`, and each line of an attack after the first moves it with
+    /// probability `inner`.
+    Messy { change: f64, inner: f64 },
+    /// Every garbage line has its hole in column `column`, which must be less
+    /// than the width of the board
+    Fixed(usize),
+}
+
+impl GarbageHole {
+    /// Gives every attack a random hole column, shared by all of its lines
+    pub const CLEAN: GarbageHole = GarbageHole::Messy {
+        change: 1.0,
+        inner: 0.0,
+    };
+}
+
+impl Default for GarbageHole {
+    fn default() -> Self {
+        Self::CLEAN
+    }
+}
+
+/// Lines of incoming garbage from a single attack, which start with the same
+/// hole column
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Garbage {
+    lines: u32,
+    hole: usize,
+    ready_at: usize,
+}
+
+impl Garbage {
+    #[must_use]
+    /// Creates an attack of `lines` garbage lines with a hole in column
+    /// `hole`, which can be inserted into the board from frame `ready_at`
+    pub fn new(lines: u32, hole: usize, ready_at: usize) -> Garbage {
+        Garbage {
+            lines,
+            hole,
+            ready_at,
+        }
+    }
+
+    #[must_use]
+    /// Returns the number of garbage lines
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    #[must_use]
+    /// Returns the column of the hole in the next garbage line
+    pub fn hole(&self) -> usize {
+        self.hole
+    }
+
+    #[must_use]
+    /// Returns the frame from which the garbage can be inserted into the
+    /// board
+    pub fn ready_at(&self) -> usize {
+        self.ready_at
+    }
+}
+
+impl TetrisGame {
+    /// Queues `lines` lines of incoming garbage, with the hole column chosen
+    /// according to `GameConfig::garbage_hole`. Once `GameConfig::garbage_delay`
+    /// frames have passed, the garbage is inserted the next time a piece locks
+    /// without clearing lines, unless it is cancelled by outgoing attack
+    /// first.
+    pub fn receive_garbage(&mut self, lines: u32) {
+        if lines == 0 {
+            return;
+        }
+
+        let hole = match self.config.garbage_hole {
+            GarbageHole::Messy { change, .. } => self.move_hole(self.last_hole, change),
+            GarbageHole::Fixed(column) => column,
+        };
+        self.last_hole = Some(hole);
+        let ready_at = self.frame + self.config.garbage_delay;
+        self.incoming_garbage
+            .push_back(Garbage::new(lines, hole, ready_at));
+    }
+
+    /// Returns the queue of incoming garbage, oldest attack first
+    #[must_use]
+    pub fn incoming_garbage(&self) -> &VecDeque<Garbage> {
+        &self.incoming_garbage
+    }
+
+    /// Returns the total number of incoming garbage lines
+    #[must_use]
+    pub fn pending_garbage(&self) -> u32 {
+        self.incoming_garbage.iter().map(Garbage::lines).sum()
+    }
+
+    /// Returns the attacks sent since the last call, oldest first, leaving
+    /// none queued. Each attack is the number of garbage lines left over
+    /// after cancelling incoming garbage.
+    pub fn take_outgoing_attacks(&mut self) -> Vec<u32> {
+        mem::take(&mut self.outgoing_attacks)
+    }
+
+    /// Returns `hole` moved to a different random column with probability
+    /// `chance`, or a random column if there is no hole yet
+    fn move_hole(&mut self, hole: Option<usize>, chance: f64) -> usize {
+        let width = usize::from(self.board.width());
+
+        match hole {
+            Some(hole) if !self.garbage_rng.next_bool(chance) => hole,
+            Some(hole) => {
+                let column = self.garbage_rng.next_index(width - 1);
+                if column >= hole { column + 1 } else { column }
+            }
+            None => self.garbage_rng.next_index(width),
+        }
+    }
+
+    /// Cancels incoming garbage with `attack` lines, oldest garbage first,
+    /// returning the lines left over
+    fn cancel_garbage(&mut self, mut attack: u32) -> u32 {
+        while attack > 0 {
+            let Some(garbage) = self.incoming_garbage.front_mut() else {
+                break;
+            };
+
+            let cancelled = attack.min(garbage.lines);
+            attack -= cancelled;
+            garbage.lines -= cancelled;
+            if garbage.lines == 0 {
+                self.incoming_garbage.pop_front();
+            }
+        }
+        attack
+    }
+
+    /// Cancels incoming garbage with the attack of `placement`, oldest attack
+    /// first, recording the lines left over as sent. Surges released by the
+    /// placement cancel garbage and are sent as separate attacks. If the placement cleared
+    /// no lines, up to `GameConfig::garbage_cap` lines of ready garbage are
+    /// then inserted into the board, ending the game if they push the stack
+    /// off the top of the board.
+    pub(super) fn update_garbage(&mut self, placement: &mut PlacementResult) {
+        let surge = placement.surge();
+        let attacks = iter::once(placement.attack() - surge).chain(surge_segments(surge));
+
+        let mut sent = 0;
+        for attack in attacks {
+            let attack = self.cancel_garbage(attack);
+            if attack > 0 {
+                self.outgoing_attacks.push(attack);
+                sent += attack;
+            }
+        }
+        placement.set_sent(sent);
+
+        if placement.lines_cleared() > 0 {
+            return;
+        }
+
+        let inner = match self.config.garbage_hole {
+            GarbageHole::Messy { inner, .. } => inner,
+            GarbageHole::Fixed(_) => 0.0,
+        };
+
+        let mut cap = self.config.garbage_cap;
+        while cap > 0 {
+            let Some(&Garbage {
+                lines,
+                mut hole,
+                ready_at,
+            }) = self.incoming_garbage.front()
+            else {
+                break;
+            };
+            if ready_at > self.frame {
+                break;
+            }
+
+            let inserted = cap.min(lines);
+            for line in 0..inserted {
+                if line > 0 {
+                    hole = self.move_hole(Some(hole), inner);
+                }
+                if self.board.insert_garbage(1, hole) {
+                    self.end(GameEndReason::TopOut);
+                }
+            }
+            cap -= inserted;
+
+            if inserted == lines {
+                self.incoming_garbage.pop_front();
+            } else if let Some(garbage) = self.incoming_garbage.front_mut() {
+                garbage.lines -= inserted;
+                garbage.hole = hole;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Controllable, Game, GameConfig};
+
+    /// Creates a started game whose garbage holes are always in column 0
+    fn fixed_hole_game(garbage_cap: u32) -> TetrisGame {
+        let mut game = TetrisGame::with_config(GameConfig {
+            garbage_hole: GarbageHole::Fixed(0),
+            garbage_cap,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        game
+    }
+
+    /// Creates a started game with garbage messiness `change` and `inner`,
+    /// seeded with `seed`
+    fn messy_game(change: f64, inner: f64, seed: u64) -> TetrisGame {
+        let mut game = TetrisGame::with_config(GameConfig {
+            garbage_hole: GarbageHole::Messy { change, inner },
+            seed: Some(seed),
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        game
+    }
+
+    /// Returns the hole columns of the garbage rows at the bottom of the
+    /// board, from the bottom up
+    fn garbage_holes(game: &TetrisGame) -> Vec<usize> {
+        game.board()
+            .cells()
+            .iter()
+            .rev()
+            .map_while(|row| {
+                let mut holes = (0..10).filter(|&col| row[col].is_empty());
+                match (holes.next(), holes.next()) {
+                    (Some(hole), None) => Some(hole),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of garbage rows at the bottom of the board
+    fn garbage_rows(game: &TetrisGame) -> usize {
+        game.board()
+            .cells()
+            .iter()
+            .rev()
+            .take_while(|row| row[0].is_empty() && row[1..].iter().all(|cell| cell.is_filled()))
+            .count()
+    }
+
+    #[test]
+    fn garbage_inserted_on_lock() {
+        let mut game = fixed_hole_game(8);
+        game.receive_garbage(3);
+        assert_eq!(3, game.pending_garbage());
+
+        game.hard_drop().unwrap();
+        assert_eq!(
+            3,
+            garbage_rows(&game),
+            "Garbage should be inserted when a piece locks without clearing lines"
+        );
+        assert_eq!(0, game.pending_garbage());
+    }
+
+    #[test]
+    fn garbage_cap() {
+        let mut game = fixed_hole_game(8);
+        game.receive_garbage(6);
+        game.receive_garbage(4);
+
+        game.hard_drop().unwrap();
+        assert_eq!(
+            8,
+            garbage_rows(&game),
+            "No more than the garbage cap should be inserted per placement"
+        );
+        assert_eq!(
+            &VecDeque::from([Garbage::new(2, 0, 0)]),
+            game.incoming_garbage(),
+            "The rest of a partially inserted attack should stay queued"
+        );
+    }
+
+    #[test]
+    fn attack_cancels_garbage() {
+        let mut game = fixed_hole_game(8);
+        game.receive_garbage(1);
+        game.receive_garbage(2);
+        game.fill_well(19);
+        game.drop_i_in_well();
+
+        let placement = game.last_placement().unwrap();
+        assert_eq!(4, placement.attack());
+        assert_eq!(
+            1,
+            placement.sent(),
+            "Attack left over after cancelling all incoming garbage should be sent"
+        );
+        assert!(game.incoming_garbage().is_empty());
+        assert_eq!(vec![1], game.take_outgoing_attacks());
+        assert!(
+            game.take_outgoing_attacks().is_empty(),
+            "Taking the outgoing attacks should leave none queued"
+        );
+    }
+
+    #[test]
+    fn line_clears_block_garbage() {
+        let mut game = fixed_hole_game(8);
+        game.receive_garbage(5);
+        game.fill_well(19);
+        game.drop_i_in_well();
+
+        assert_eq!(0, game.last_placement().unwrap().sent());
+        assert_eq!(
+            1,
+            game.pending_garbage(),
+            "Garbage left over after cancelling should not be inserted on a line clear"
+        );
+        assert_eq!(0, garbage_rows(&game));
+    }
+
+    #[test]
+    fn garbage_top_out() {
+        let mut game = fixed_hole_game(8);
+        game.fill_well(6);
+        game.receive_garbage(6);
+        game.hard_drop().unwrap();
+
+        assert_eq!(
+            Some(GameEndReason::TopOut),
+            game.end_reason(),
+            "Garbage pushing the stack off the top of the board should end the game"
+        );
+    }
+
+    #[test]
+    fn seeded_garbage_is_identical() {
+        let mut first = messy_game(0.5, 0.5, 42);
+        let mut second = messy_game(0.5, 0.5, 42);
+
+        for lines in 1..=6 {
+            first.receive_garbage(lines);
+            second.receive_garbage(lines);
+        }
+        assert_eq!(first.incoming_garbage(), second.incoming_garbage());
+
+        first.hard_drop().unwrap();
+        second.hard_drop().unwrap();
+        assert_eq!(
+            garbage_holes(&first),
+            garbage_holes(&second),
+            "Games with the same seed should insert identical garbage"
+        );
+    }
+
+    #[test]
+    fn seeded_garbage_is_stable() {
+        let mut game = messy_game(1.0, 0.0, 1);
+        for _ in 0..6 {
+            game.receive_garbage(1);
+        }
+
+        let holes: Vec<usize> = game.incoming_garbage().iter().map(Garbage::hole).collect();
+        assert_eq!(
+            vec![0, 7, 4, 0, 7, 3],
+            holes,
+            "Seeded garbage holes should not change between versions"
+        );
+    }
+
+    #[test]
+    fn change_messiness() {
+        let mut clean = messy_game(0.0, 0.0, 1);
+        let mut messy = messy_game(1.0, 0.0, 1);
+        for _ in 0..10 {
+            clean.receive_garbage(1);
+            messy.receive_garbage(1);
+        }
+
+        let holes: Vec<usize> = clean.incoming_garbage().iter().map(Garbage::hole).collect();
+        assert!(
+            holes.windows(2).all(|pair| pair[0] == pair[1]),
+            "Attacks should share a hole column without change messiness, were {holes:?}"
+        );
+
+        let holes: Vec<usize> = messy.incoming_garbage().iter().map(Garbage::hole).collect();
+        assert!(
+            holes.windows(2).all(|pair| pair[0] != pair[1]),
+            "Every attack should move the hole with full change messiness, were {holes:?}"
+        );
+    }
+
+    #[test]
+    fn inner_messiness() {
+        let mut clean = messy_game(0.0, 0.0, 1);
+        let mut messy = messy_game(0.0, 1.0, 1);
+        clean.receive_garbage(6);
+        messy.receive_garbage(6);
+        clean.hard_drop().unwrap();
+        messy.hard_drop().unwrap();
+
+        let holes = garbage_holes(&clean);
+        assert_eq!(6, holes.len());
+        assert!(
+            holes.windows(2).all(|pair| pair[0] == pair[1]),
+            "Lines of an attack should share a hole without inner messiness, were {holes:?}"
+        );
+
+        let holes = garbage_holes(&messy);
+        assert_eq!(6, holes.len());
+        assert!(
+            holes.windows(2).all(|pair| pair[0] != pair[1]),
+            "Every line should move the hole with full inner messiness, were {holes:?}"
+        );
+    }
+
+    #[test]
+    fn garbage_travel_delay() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            garbage_hole: GarbageHole::Fixed(0),
+            garbage_delay: 2,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        game.receive_garbage(2);
+        assert_eq!(2, game.incoming_garbage()[0].ready_at());
+
+        game.next_frame(&mut VecDeque::new()).unwrap();
+        game.hard_drop().unwrap();
+        assert_eq!(
+            0,
+            garbage_rows(&game),
+            "Garbage should not be inserted before it is ready"
+        );
+        assert_eq!(2, game.pending_garbage());
+
+        game.next_frame(&mut VecDeque::new()).unwrap();
+        game.hard_drop().unwrap();
+        assert_eq!(
+            2,
+            garbage_rows(&game),
+            "Garbage should be inserted once it is ready"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+/// The speed the active piece falls at without soft dropping
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Gravity {
+    /// A constant speed, in cells per frame
+    Fixed(f64),
+    /// The guideline marathon speed curve, which gets faster with each level
+    Guideline,
+    /// TETR.IO's gravity settings, starting at `gravity` cells per frame and
+    /// increasing by `increase` cells per frame every frame once `margin`
+    /// frames have passed
+    Tetrio {
+        gravity: f64,
+        increase: f64,
+        margin: usize,
+    },
+}
+
+impl Gravity {
+    /// Drops the active piece to the bottom of the board every frame
+    pub const TWENTY_G: Gravity = Gravity::Fixed(20.0);
+
+    #[must_use]
+    /// Returns the gravity in cells per frame at `level` on frame `frame`,
+    /// capped at 20G. Pieces fall to the bottom of the board instantly at 20G,
+    /// even though the board is taller than 20 rows.
+    pub fn cells_per_frame(&self, level: u32, frame: usize) -> f64 {
+        let gravity = match *self {
+            Gravity::Fixed(gravity) => gravity,
+            Gravity::Guideline => {
+                let level = f64::from(level.max(1) - 1);
+                let seconds_per_row = (0.8 - level * 0.007).powf(level);
+                1.0 / (seconds_per_row * 60.0)
+            }
+            Gravity::Tetrio {
+                gravity,
+                increase,
+                margin,
+            } => {
+                let frames_increased =
+                    u32::try_from(frame.saturating_sub(margin)).unwrap_or(u32::MAX);
+                gravity + increase * f64::from(frames_increased)
+            }
+        };
+
+        gravity.min(20.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameConfig, TetrisGame};
+
+    use std::collections::VecDeque;
+
+    #[test]
+    fn guideline_speed_curve() {
+        let expected_gravity = [
+            (1, 1.0 / 60.0),
+            (2, 0.021_017),
+            (5, 0.046_922),
+            (10, 0.259_801),
+            (15, 2.361_180),
+        ];
+
+        for (level, expected) in expected_gravity {
+            let gravity = Gravity::Guideline.cells_per_frame(level, 0);
+            assert!(
+                (gravity - expected).abs() < 1e-4,
+                "Incorrect guideline gravity at level {level}. Expected {expected}, was {gravity}"
+            );
+        }
+
+        assert!(
+            (Gravity::Guideline.cells_per_frame(30, 0) - 20.0).abs() < f64::EPSILON,
+            "Guideline gravity should be capped at 20G"
+        );
+    }
+
+    #[test]
+    fn tetrio_gravity_increase() {
+        let gravity = Gravity::Tetrio {
+            gravity: 0.02,
+            increase: 0.001,
+            margin: 60,
+        };
+
+        assert!(
+            (gravity.cells_per_frame(1, 30) - 0.02).abs() < 1e-9,
+            "TETR.IO gravity should not increase before the margin time"
+        );
+        assert!(
+            (gravity.cells_per_frame(1, 160) - 0.12).abs() < 1e-9,
+            "TETR.IO gravity should increase every frame after the margin time"
+        );
+    }
+
+    #[test]
+    fn pieces_fall_with_gravity() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            gravity: Gravity::Fixed(0.5),
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        let start_y = game.active_piece().unwrap().y();
+
+        for frame in 1..=6 {
+            game.next_frame(&mut VecDeque::new()).unwrap();
+            assert_eq!(
+                start_y + frame / 2,
+                game.active_piece().unwrap().y(),
+                "Active piece should fall one cell every two frames at 0.5G, \
+                but it was at an incorrect y-position after {frame} frames"
+            );
+        }
+    }
+
+    #[test]
+    fn twenty_g_lands_instantly() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            gravity: Gravity::TWENTY_G,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        game.next_frame(&mut VecDeque::new()).unwrap();
+
+        let mut piece = game.active_piece().unwrap().clone();
+        assert!(
+            piece.move_down(game.board()).is_err(),
+            "Active piece should reach the stack within one frame at 20G"
+        );
+    }
+
+    #[test]
+    fn level_increases_gravity() {
+        let mut game = TetrisGame::new(7, 5, None);
+        assert_eq!(1, game.level());
+
+        game.lines = 25;
+        assert_eq!(3, game.level(), "Level should increase every 10 lines");
+
+        game.config.start_level = 5;
+        assert_eq!(
+            7,
+            game.level(),
+            "Level should increase every 10 lines from the start level"
+        );
+    }
+}
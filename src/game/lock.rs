@@ -0,0 +1,215 @@
+use crate::{Board, Piece, TetrisError, TetrisGame};
+
+/// When moving or rotating the active piece while it rests on the stack
+/// resets its lock delay
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LockReset {
+    /// Moves and rotations reset the lock delay up to `limit` times, and the
+    /// count is restored whenever the piece falls to a new lowest row
+    Move { limit: u32 },
+    /// Only falling to a new lowest row resets the lock delay
+    Step,
+    /// Moves and rotations always reset the lock delay
+    Infinite,
+}
+
+impl TetrisGame {
+    /// Returns the number of frames the active piece can rest on the stack
+    /// before locking
+    #[must_use]
+    pub fn lock_delay_remaining(&self) -> u32 {
+        self.lock_timer
+    }
+
+    /// Returns the number of lock delay resets the active piece has left, or
+    /// `None` if it has no limit
+    #[must_use]
+    pub fn lock_resets_remaining(&self) -> Option<u32> {
+        match self.config.lock_reset {
+            LockReset::Move { limit } => Some(limit.saturating_sub(self.lock_resets)),
+            LockReset::Step => Some(0),
+            LockReset::Infinite => None,
+        }
+    }
+
+    /// Applies `manipulation` to the active piece, resetting the lock delay
+    /// if it succeeds while the piece rests on the stack
+    pub(super) fn manipulate(
+        &mut self,
+        manipulation: impl FnOnce(&mut Piece, &Board) -> Result<(), TetrisError>,
+    ) -> Result<(), TetrisError> {
+        let piece = self
+            .active_piece
+            .as_mut()
+            .ok_or(TetrisError::GameNotStarted)?;
+        let grounded = is_grounded(piece, &self.board);
+
+        manipulation(piece, &self.board)?;
+
+        if grounded {
+            match self.config.lock_reset {
+                LockReset::Move { limit } if self.lock_resets < limit => {
+                    self.lock_timer = self.config.lock_delay;
+                    self.lock_resets += 1;
+                }
+                LockReset::Infinite => self.lock_timer = self.config.lock_delay,
+                LockReset::Move { .. } | LockReset::Step => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restores the lock delay and resets for a newly spawned active piece
+    pub(super) fn reset_lock_delay(&mut self) {
+        self.lock_timer = self.config.lock_delay;
+        self.lock_resets = 0;
+        self.lowest_y = self.active_piece.as_ref().map_or(0, Piece::y);
+    }
+
+    /// Counts down the lock delay while the active piece rests on the stack,
+    /// locking it once the delay expires
+    pub(super) fn update_lock_delay(&mut self) -> Result<(), TetrisError> {
+        let Some(piece) = self.active_piece.as_ref() else {
+            return Ok(());
+        };
+        let grounded = is_grounded(piece, &self.board);
+
+        if piece.y() > self.lowest_y {
+            self.reset_lock_delay();
+        }
+
+        if !grounded {
+            return Ok(());
+        }
+
+        self.lock_timer = self.lock_timer.saturating_sub(1);
+        if self.lock_timer == 0 {
+            self.lock_active_piece(false)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if `piece` cannot move down any further
+fn is_grounded(piece: &Piece, board: &Board) -> bool {
+    piece.clone().move_down(board).is_err()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameConfig, Gravity, TetrisInput};
+
+    use std::collections::VecDeque;
+
+    fn twenty_g_game(lock_reset: LockReset) -> TetrisGame {
+        let mut game = TetrisGame::with_config(GameConfig {
+            gravity: Gravity::TWENTY_G,
+            lock_delay: 5,
+            lock_reset,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        game
+    }
+
+    fn board_is_empty(game: &TetrisGame) -> bool {
+        game.board()
+            .cells()
+            .iter()
+            .flatten()
+            .all(|cell| cell.is_empty())
+    }
+
+    /// Advances `game` by `frames` frames, alternating between moving left
+    /// and right each frame
+    fn wiggle(game: &mut TetrisGame, frames: usize) {
+        for frame in 0..frames {
+            let input = if frame % 2 == 0 {
+                TetrisInput::MoveLeft
+            } else {
+                TetrisInput::MoveRight
+            };
+            game.next_frame(&mut VecDeque::from([input])).unwrap();
+        }
+    }
+
+    #[test]
+    fn lock_delay_expires() {
+        let mut game = twenty_g_game(LockReset::Move { limit: 15 });
+
+        game.next_frame(&mut VecDeque::new()).unwrap();
+        assert_eq!(
+            4,
+            game.lock_delay_remaining(),
+            "Lock delay should count down once the active piece lands"
+        );
+
+        for _ in 0..3 {
+            game.next_frame(&mut VecDeque::new()).unwrap();
+        }
+        assert!(
+            board_is_empty(&game),
+            "Active piece should not lock before its lock delay expires"
+        );
+
+        game.next_frame(&mut VecDeque::new()).unwrap();
+        assert!(
+            !board_is_empty(&game),
+            "Active piece should lock once its lock delay expires"
+        );
+        assert_eq!(
+            5,
+            game.lock_delay_remaining(),
+            "Lock delay should be restored for the next piece"
+        );
+    }
+
+    #[test]
+    fn move_reset_limit() {
+        let mut game = twenty_g_game(LockReset::Move { limit: 2 });
+        game.next_frame(&mut VecDeque::new()).unwrap();
+
+        wiggle(&mut game, 5);
+        assert!(
+            board_is_empty(&game),
+            "Moving the active piece should reset its lock delay twice"
+        );
+        assert_eq!(Some(0), game.lock_resets_remaining());
+
+        wiggle(&mut game, 1);
+        assert!(
+            !board_is_empty(&game),
+            "Moving the active piece should not reset its lock delay after reaching the limit"
+        );
+    }
+
+    #[test]
+    fn step_reset_ignores_moves() {
+        let mut game = twenty_g_game(LockReset::Step);
+        game.next_frame(&mut VecDeque::new()).unwrap();
+
+        wiggle(&mut game, 3);
+        assert!(board_is_empty(&game));
+        wiggle(&mut game, 1);
+        assert!(
+            !board_is_empty(&game),
+            "Moving the active piece should not reset its lock delay with step reset"
+        );
+    }
+
+    #[test]
+    fn infinite_reset() {
+        let mut game = twenty_g_game(LockReset::Infinite);
+        game.next_frame(&mut VecDeque::new()).unwrap();
+
+        wiggle(&mut game, 100);
+        assert!(
+            board_is_empty(&game),
+            "Moving the active piece should always reset its lock delay with infinite reset"
+        );
+        assert_eq!(None, game.lock_resets_remaining());
+    }
+}
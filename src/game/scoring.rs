@@ -0,0 +1,166 @@
+use crate::{PlacementResult, Spin, TetrisGame};
+
+/// The points awarded per consecutive line clear after the first
+const COMBO_POINTS: u64 = 50;
+
+/// Returns the guideline points for clearing `lines` with `spin`, before the
+/// back-to-back bonus and the level multiplier
+fn clear_points(lines: usize, spin: Spin) -> u64 {
+    let points: [u64; 5] = match spin {
+        Spin::None => [0, 100, 300, 500, 800],
+        Spin::Mini => [100, 200, 400, 400, 400],
+        Spin::Full => [400, 800, 1200, 1600, 1600],
+    };
+    points[lines.min(4)]
+}
+
+/// Returns the guideline bonus for a perfect clear of `lines`, before the
+/// level multiplier
+fn perfect_clear_points(lines: usize, back_to_back: bool) -> u64 {
+    match lines {
+        1 => 800,
+        2 => 1200,
+        3 => 1800,
+        _ if back_to_back => 3200,
+        _ => 2000,
+    }
+}
+
+impl TetrisGame {
+    /// Returns the total number of points scored
+    #[must_use]
+    pub fn score(&self) -> u64 {
+        self.score
+    }
+
+    /// Awards the guideline points for `placement` at the current level,
+    /// using the combo and back-to-back chain recorded in it
+    pub(super) fn score_placement(&mut self, placement: &PlacementResult) {
+        let lines = placement.lines_cleared();
+        let level = u64::from(self.level());
+        let back_to_back =
+            placement.difficult() && placement.back_to_back().is_some_and(|chain| chain > 0);
+
+        let mut points = clear_points(lines, placement.spin());
+        if back_to_back {
+            points += points / 2;
+        }
+        if let Some(combo) = placement.combo() {
+            points += COMBO_POINTS * u64::from(combo);
+        }
+        if placement.perfect_clear() {
+            points += perfect_clear_points(lines, back_to_back);
+        }
+
+        self.score += points * level;
+    }
+
+    /// Awards one point per cell soft dropped, or two per cell hard dropped
+    pub(super) fn score_drop(&mut self, cells: u32, hard_drop: bool) {
+        let points_per_cell = if hard_drop { 2 } else { 1 };
+        self.score += u64::from(cells) * points_per_cell;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameConfig, Handling, SoftDropFactor, TetrisInput};
+
+    use std::collections::VecDeque;
+
+    /// The number of cells a vertical I piece falls from its spawn row to the
+    /// floor
+    const I_DROP_DISTANCE: u64 = 19;
+
+    #[test]
+    fn clear_points_table() {
+        let expected = [
+            (Spin::None, [0, 100, 300, 500, 800]),
+            (Spin::Mini, [100, 200, 400, 400, 400]),
+            (Spin::Full, [400, 800, 1200, 1600, 1600]),
+        ];
+
+        for (spin, points) in expected {
+            for (lines, points) in points.into_iter().enumerate() {
+                assert_eq!(
+                    points,
+                    clear_points(lines, spin),
+                    "Incorrect points for clearing {lines} lines with {spin:?} spin"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn back_to_back_tetris_with_combo() {
+        let mut game = TetrisGame::new(7, 5, None);
+        game.start().unwrap();
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(
+            800 + 2 * I_DROP_DISTANCE,
+            game.score(),
+            "A tetris should score 800 points plus 2 points per hard dropped cell"
+        );
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(
+            800 + 1200 + 50 + 4 * I_DROP_DISTANCE,
+            game.score(),
+            "A back-to-back tetris should score 1.5x plus the combo bonus"
+        );
+    }
+
+    #[test]
+    fn perfect_clear_bonus() {
+        let mut game = TetrisGame::new(7, 5, None);
+        game.start().unwrap();
+
+        game.fill_well(20);
+        game.drop_i_in_well();
+        assert_eq!(
+            800 + 2000 + 2 * I_DROP_DISTANCE,
+            game.score(),
+            "A perfect clear tetris should add a 2000 point bonus"
+        );
+    }
+
+    #[test]
+    fn points_scale_with_level() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            start_level: 3,
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+
+        game.fill_well(19);
+        game.drop_i_in_well();
+        assert_eq!(3 * 800 + 2 * I_DROP_DISTANCE, game.score());
+    }
+
+    #[test]
+    fn soft_drop_points() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            handling: Handling {
+                sdf: SoftDropFactor::Infinite,
+                ..Handling::default()
+            },
+            ..GameConfig::default()
+        });
+        game.start().unwrap();
+        let start_y = game.active_piece().unwrap().y();
+
+        game.next_frame(&mut VecDeque::from([TetrisInput::SoftDrop]))
+            .unwrap();
+        let dropped = game.active_piece().unwrap().y() - start_y;
+
+        assert_eq!(
+            u64::try_from(dropped).unwrap(),
+            game.score(),
+            "Soft dropping should score 1 point per cell"
+        );
+    }
+}
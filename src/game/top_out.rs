@@ -0,0 +1,133 @@
+use crate::{Position, TetrisGame};
+
+/// The number of rows above the visible 20 rows of the board
+const HIDDEN_ROWS: i32 = 4;
+
+/// The reason a game ended
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GameEndReason {
+    /// A new piece spawned overlapping the stack
+    BlockOut,
+    /// A piece locked entirely above the visible 20 rows
+    LockOut,
+    /// A piece locked partially above the visible 20 rows, which only ends
+    /// the game if `GameConfig::partial_lock_out` is set
+    PartialLockOut,
+    /// Incoming garbage pushed the stack off the top of the board
+    TopOut,
+    /// The game was ended by the player
+    Forfeit,
+}
+
+impl TetrisGame {
+    /// Returns the reason the game ended, or `None` if it is still running
+    #[must_use]
+    pub fn end_reason(&self) -> Option<GameEndReason> {
+        self.end_reason
+    }
+
+    /// Ends the game for `reason` unless it has already ended
+    pub(super) fn end(&mut self, reason: GameEndReason) {
+        self.end_reason.get_or_insert(reason);
+    }
+
+    /// Returns the lock out caused by a piece locking at `cells`, or `None`
+    /// if it locked within the visible rows
+    pub(super) fn lock_out(&self, cells: &[Position; 4]) -> Option<GameEndReason> {
+        let hidden_cells = cells.iter().filter(|pos| pos.y() < HIDDEN_ROWS).count();
+
+        if hidden_cells == 4 {
+            Some(GameEndReason::LockOut)
+        } else if hidden_cells > 0 && self.config.partial_lock_out {
+            Some(GameEndReason::PartialLockOut)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Controllable, Game, GameConfig, Piece, PieceType, TetrisError, TetrisInput};
+
+    use std::collections::VecDeque;
+
+    #[test]
+    fn block_out() {
+        let mut game = TetrisGame::new_tetrio(None);
+        game.fill_well(1);
+        game.start().unwrap();
+
+        assert_eq!(
+            Some(GameEndReason::BlockOut),
+            game.end_reason(),
+            "Spawning a piece overlapping the stack should end the game with a block out"
+        );
+        assert_eq!(
+            Err(TetrisError::GameOver),
+            game.next_frame(&mut VecDeque::new())
+        );
+    }
+
+    #[test]
+    fn lock_out() {
+        let mut game = TetrisGame::new_tetrio(None);
+        game.fill_well(4);
+        game.start().unwrap();
+        game.active_piece = Some(Piece::new(PieceType::T));
+
+        assert_eq!(Ok(()), game.hard_drop());
+        assert_eq!(
+            Some(GameEndReason::LockOut),
+            game.end_reason(),
+            "Locking a piece entirely above the visible rows should end the game with a lock out"
+        );
+        assert!(
+            game.active_piece().is_none(),
+            "No piece should spawn after a lock out"
+        );
+    }
+
+    #[test]
+    fn partial_lock_out() {
+        for partial_lock_out in [false, true] {
+            let mut game = TetrisGame::with_config(GameConfig {
+                partial_lock_out,
+                ..GameConfig::tetrio()
+            });
+            game.fill_well(5);
+            game.start().unwrap();
+            game.active_piece = Some(Piece::new(PieceType::T));
+
+            assert_eq!(Ok(()), game.hard_drop());
+            assert_eq!(
+                partial_lock_out.then_some(GameEndReason::PartialLockOut),
+                game.end_reason(),
+                "Locking a piece partially above the visible rows should only end the game \
+                when partial lock out is enabled"
+            );
+        }
+    }
+
+    #[test]
+    fn forfeit() {
+        let mut game = TetrisGame::new_tetrio(None);
+        game.start().unwrap();
+        game.next_frame(&mut VecDeque::from([
+            TetrisInput::Forfeit,
+            TetrisInput::HardDrop,
+        ]))
+        .unwrap();
+
+        assert_eq!(Some(GameEndReason::Forfeit), game.end_reason());
+        assert!(
+            game.board()
+                .cells()
+                .iter()
+                .flatten()
+                .all(|cell| cell.is_empty()),
+            "Inputs after a forfeit should not be executed"
+        );
+    }
+}
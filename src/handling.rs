@@ -0,0 +1,425 @@
+use crate::TetrisInput;
+
+use std::collections::VecDeque;
+
+/// A key that controls the game
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Key {
+    Left,
+    Right,
+    SoftDrop,
+    HardDrop,
+    RotateCW,
+    RotateCCW,
+    Rotate180,
+    Hold,
+}
+
+/// A change in the state of a key during a frame
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum KeyEvent {
+    Press(Key),
+    Release(Key),
+}
+
+/// How much faster than gravity the active piece falls while soft dropping
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SoftDropFactor {
+    /// Soft dropping multiplies gravity by the factor
+    Factor(f64),
+    /// Soft dropping moves the active piece to the lowest position it can
+    /// reach within the same frame
+    Infinite,
+}
+
+/// Settings that determine how held keys are turned into movement, matching
+/// TETR.IO's handling options. All delays are in frames.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Handling {
+    /// Delayed auto shift, the delay before a held direction starts repeating
+    pub das: u32,
+    /// Auto repeat rate, the delay between repeated moves. An ARR of 0 moves
+    /// the active piece to the wall instantly.
+    pub arr: u32,
+    /// DAS cut delay, the delay before a charged direction resumes repeating
+    /// after rotating, holding or hard dropping
+    pub dcd: u32,
+    /// How much faster than gravity the active piece falls while soft
+    /// dropping
+    pub sdf: SoftDropFactor,
+    /// Whether pressing a new direction discards the DAS charged by the
+    /// previous one
+    pub cancel_das_on_direction_change: bool,
+}
+
+impl Handling {
+    /// Creates TETR.IO's default handling, with a DAS of 10, an ARR of 2, a DCD
+    /// of 1 and a soft drop factor of 6
+    #[must_use]
+    pub fn tetrio() -> Self {
+        Handling {
+            das: 10,
+            arr: 2,
+            dcd: 1,
+            sdf: SoftDropFactor::Factor(6.0),
+            cancel_das_on_direction_change: false,
+        }
+    }
+}
+
+impl Default for Handling {
+    /// Creates guideline handling, with a DAS of 10, an ARR of 2, no DCD and a
+    /// soft drop factor of 20
+    fn default() -> Self {
+        Handling {
+            das: 10,
+            arr: 2,
+            dcd: 0,
+            sdf: SoftDropFactor::Factor(20.0),
+            cancel_das_on_direction_change: false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Direction {
+    Left,
+    Right,
+}
+
+/// Tracks which keys are held and turns key events into the inputs executed
+/// each frame
+#[derive(Debug, Clone)]
+pub struct InputHandler {
+    handling: Handling,
+    left_held: bool,
+    right_held: bool,
+    soft_drop_held: bool,
+    direction: Option<Direction>,
+    charge: u32,
+}
+
+impl InputHandler {
+    #[must_use]
+    /// Creates a new input handler with no keys held
+    pub fn new(handling: Handling) -> InputHandler {
+        InputHandler {
+            handling,
+            left_held: false,
+            right_held: false,
+            soft_drop_held: false,
+            direction: None,
+            charge: 0,
+        }
+    }
+
+    #[must_use]
+    /// Returns the handling settings
+    pub fn handling(&self) -> &Handling {
+        &self.handling
+    }
+
+    /// Applies the key events of a frame in order, returning the inputs to
+    /// execute during that frame
+    pub fn frame(&mut self, events: &[KeyEvent]) -> VecDeque<TetrisInput> {
+        let mut inputs = VecDeque::new();
+        let mut tapped = false;
+
+        for &event in events {
+            match event {
+                KeyEvent::Press(Key::Left) => {
+                    self.left_held = true;
+                    self.press_direction(Direction::Left);
+                    inputs.push_back(TetrisInput::MoveLeft);
+                    tapped = true;
+                }
+                KeyEvent::Press(Key::Right) => {
+                    self.right_held = true;
+                    self.press_direction(Direction::Right);
+                    inputs.push_back(TetrisInput::MoveRight);
+                    tapped = true;
+                }
+                KeyEvent::Release(Key::Left) => {
+                    self.left_held = false;
+                    self.release_direction(Direction::Left, Direction::Right, self.right_held);
+                }
+                KeyEvent::Release(Key::Right) => {
+                    self.right_held = false;
+                    self.release_direction(Direction::Right, Direction::Left, self.left_held);
+                }
+                KeyEvent::Press(Key::SoftDrop) => self.soft_drop_held = true,
+                KeyEvent::Release(Key::SoftDrop) => self.soft_drop_held = false,
+                KeyEvent::Press(Key::HardDrop) => self.act(&mut inputs, TetrisInput::HardDrop),
+                KeyEvent::Press(Key::RotateCW) => self.act(&mut inputs, TetrisInput::RotateCW),
+                KeyEvent::Press(Key::RotateCCW) => self.act(&mut inputs, TetrisInput::RotateCCW),
+                KeyEvent::Press(Key::Rotate180) => self.act(&mut inputs, TetrisInput::Rotate180),
+                KeyEvent::Press(Key::Hold) => self.act(&mut inputs, TetrisInput::Hold),
+                KeyEvent::Release(_) => (),
+            }
+        }
+
+        if !tapped {
+            self.auto_repeat(&mut inputs);
+        }
+        if self.soft_drop_held {
+            inputs.push_back(TetrisInput::SoftDrop);
+        }
+
+        inputs
+    }
+
+    fn press_direction(&mut self, direction: Direction) {
+        if self.direction.is_none() || self.handling.cancel_das_on_direction_change {
+            self.charge = 0;
+        }
+        self.direction = Some(direction);
+    }
+
+    fn release_direction(&mut self, released: Direction, other: Direction, other_held: bool) {
+        if self.direction != Some(released) {
+            return;
+        }
+
+        if other_held {
+            self.direction = Some(other);
+            if self.handling.cancel_das_on_direction_change {
+                self.charge = 0;
+            }
+        } else {
+            self.direction = None;
+            self.charge = 0;
+        }
+    }
+
+    /// Executes a rotation, hold or hard drop, delaying a charged direction by
+    /// the DCD
+    fn act(&mut self, inputs: &mut VecDeque<TetrisInput>, input: TetrisInput) {
+        inputs.push_back(input);
+
+        if self.handling.dcd > 0 && self.charge >= self.handling.das {
+            self.charge = self.handling.das.saturating_sub(self.handling.dcd);
+        }
+    }
+
+    /// Moves in the held direction once it has been held for the DAS, then
+    /// every ARR frames after
+    fn auto_repeat(&mut self, inputs: &mut VecDeque<TetrisInput>) {
+        let Some(direction) = self.direction else {
+            return;
+        };
+
+        self.charge = self.charge.saturating_add(1);
+        if self.charge < self.handling.das {
+            return;
+        }
+
+        let input = match (direction, self.handling.arr) {
+            (Direction::Left, 0) => TetrisInput::SnapLeft,
+            (Direction::Right, 0) => TetrisInput::SnapRight,
+            (_, arr) if !(self.charge - self.handling.das).is_multiple_of(arr) => return,
+            (Direction::Left, _) => TetrisInput::MoveLeft,
+            (Direction::Right, _) => TetrisInput::MoveRight,
+        };
+        inputs.push_back(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameConfig, TetrisGame};
+
+    fn handler(das: u32, arr: u32, dcd: u32, cancel_das_on_direction_change: bool) -> InputHandler {
+        InputHandler::new(Handling {
+            das,
+            arr,
+            dcd,
+            cancel_das_on_direction_change,
+            ..Handling::default()
+        })
+    }
+
+    /// Runs `handler` for one frame per element of `frames`, returning the
+    /// inputs produced on each frame
+    fn run(handler: &mut InputHandler, frames: &[&[KeyEvent]]) -> Vec<Vec<TetrisInput>> {
+        frames
+            .iter()
+            .map(|events| handler.frame(events).into_iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn das_then_arr() {
+        let mut handler = handler(3, 2, 0, false);
+        let inputs = run(
+            &mut handler,
+            &[&[KeyEvent::Press(Key::Left)], &[], &[], &[], &[], &[]],
+        );
+
+        assert_eq!(
+            vec![
+                vec![TetrisInput::MoveLeft],
+                vec![],
+                vec![],
+                vec![TetrisInput::MoveLeft],
+                vec![],
+                vec![TetrisInput::MoveLeft],
+            ],
+            inputs,
+            "Held direction should move once when pressed, then after the DAS, then every ARR frames"
+        );
+
+        let inputs = run(&mut handler, &[&[KeyEvent::Release(Key::Left)], &[]]);
+        assert_eq!(
+            vec![Vec::<TetrisInput>::new(), vec![]],
+            inputs,
+            "Released direction should stop repeating"
+        );
+    }
+
+    #[test]
+    fn zero_arr_snaps() {
+        let mut handler = handler(2, 0, 0, false);
+        let inputs = run(
+            &mut handler,
+            &[&[KeyEvent::Press(Key::Right)], &[], &[], &[]],
+        );
+
+        assert_eq!(
+            vec![
+                vec![TetrisInput::MoveRight],
+                vec![],
+                vec![TetrisInput::SnapRight],
+                vec![TetrisInput::SnapRight],
+            ],
+            inputs,
+            "Held direction with an ARR of 0 should snap to the wall once charged"
+        );
+    }
+
+    #[test]
+    fn dcd_delays_charged_direction() {
+        let frames: &[&[KeyEvent]] = &[
+            &[KeyEvent::Press(Key::Left)],
+            &[],
+            &[],
+            &[KeyEvent::Press(Key::RotateCW)],
+            &[],
+        ];
+
+        let inputs = run(&mut handler(2, 1, 0, false), frames);
+        assert_eq!(
+            vec![TetrisInput::RotateCW, TetrisInput::MoveLeft],
+            inputs[3],
+            "Charged direction should keep repeating after a rotation without DCD"
+        );
+
+        let inputs = run(&mut handler(2, 1, 2, false), frames);
+        assert_eq!(
+            vec![TetrisInput::RotateCW],
+            inputs[3],
+            "Charged direction should stop repeating after a rotation with DCD"
+        );
+        assert_eq!(
+            vec![TetrisInput::MoveLeft],
+            inputs[4],
+            "Charged direction should resume repeating once the DCD has passed"
+        );
+    }
+
+    #[test]
+    fn direction_change_keeps_or_cancels_das() {
+        let frames: &[&[KeyEvent]] = &[
+            &[KeyEvent::Press(Key::Left)],
+            &[],
+            &[],
+            &[KeyEvent::Press(Key::Right)],
+            &[],
+        ];
+
+        let inputs = run(&mut handler(2, 1, 0, false), frames);
+        assert_eq!(vec![TetrisInput::MoveRight], inputs[3]);
+        assert_eq!(
+            vec![TetrisInput::MoveRight],
+            inputs[4],
+            "New direction should use the charged DAS when DAS cancel is off"
+        );
+
+        let inputs = run(&mut handler(2, 1, 0, true), frames);
+        assert_eq!(vec![TetrisInput::MoveRight], inputs[3]);
+        assert_eq!(
+            Vec::<TetrisInput>::new(),
+            inputs[4],
+            "New direction should recharge DAS when DAS cancel is on"
+        );
+    }
+
+    #[test]
+    fn releasing_returns_to_held_direction() {
+        let mut handler = handler(2, 1, 0, true);
+        let inputs = run(
+            &mut handler,
+            &[
+                &[KeyEvent::Press(Key::Left)],
+                &[KeyEvent::Press(Key::Right)],
+                &[KeyEvent::Release(Key::Right)],
+                &[],
+            ],
+        );
+
+        assert_eq!(Vec::<TetrisInput>::new(), inputs[2]);
+        assert_eq!(
+            vec![TetrisInput::MoveLeft],
+            inputs[3],
+            "Releasing a direction should resume repeating the other held direction"
+        );
+    }
+
+    #[test]
+    fn soft_drop_while_held() {
+        let mut handler = handler(10, 2, 0, false);
+        let inputs = run(
+            &mut handler,
+            &[
+                &[KeyEvent::Press(Key::SoftDrop)],
+                &[KeyEvent::Press(Key::HardDrop)],
+                &[KeyEvent::Release(Key::SoftDrop)],
+            ],
+        );
+
+        assert_eq!(
+            vec![
+                vec![TetrisInput::SoftDrop],
+                vec![TetrisInput::HardDrop, TetrisInput::SoftDrop],
+                vec![],
+            ],
+            inputs,
+            "Soft drop should be input every frame while its key is held"
+        );
+    }
+
+    #[test]
+    fn game_handles_key_events() {
+        let mut game = TetrisGame::with_config(GameConfig {
+            handling: Handling {
+                das: 1,
+                arr: 0,
+                ..Handling::tetrio()
+            },
+            ..GameConfig::tetrio()
+        });
+        game.start().unwrap();
+        let start_x = game.active_piece().unwrap().x();
+
+        game.next_frame_with_keys(&[KeyEvent::Press(Key::Right)])
+            .unwrap();
+        assert_eq!(start_x + 1, game.active_piece().unwrap().x());
+
+        game.next_frame_with_keys(&[]).unwrap();
+        let mut piece = game.active_piece().unwrap().clone();
+        assert!(
+            piece.move_right(game.board()).is_err(),
+            "Active piece should reach the right wall once DAS is charged with an ARR of 0"
+        );
+    }
+}
@@ -3,30 +3,48 @@
 mod bag;
 mod board;
 mod game;
+mod handling;
 mod piece;
+mod randomizer;
+mod royale;
+mod versus;
 
 use std::{cell::RefMut, collections::VecDeque};
 
 // private re-exports for modules
-use bag::Bag;
+use bag::{Bag, ParkMiller};
 use board::Board;
-use piece::{Piece, PieceType};
-
-pub use game::TetrisGame;
+use piece::Piece;
+
+pub use board::{Cell, PlacementResult, Spin};
+pub use game::{
+    AttackMultiplier, BackToBack, GameConfig, GameEndReason, Garbage, GarbageHole, Gravity,
+    LockReset, TetrisGame,
+};
+pub use handling::{Handling, InputHandler, Key, KeyEvent, SoftDropFactor};
+pub use piece::{
+    KickTable, LastMove, Orientation, PieceType, Position, RotationDirection, SpinRule,
+};
+pub use randomizer::{Randomizer, RandomizerKind};
+pub use royale::{BattleRoyale, Targeting};
+pub use versus::{MatchOutcome, VersusMatch};
 
 #[derive(Debug, PartialEq)]
 pub enum TetrisError {
     InvalidRightMove,
     InvalidLeftMove,
+    InvalidDownMove,
     InvalidCWRotation,
     InvalidCCWRotation,
+    Invalid180Rotation,
     InvalidHold,
     FailedToLoadPiece,
+    InvalidPlacement,
     GameOver,
     GameNotStarted,
 }
 
-#[derive(Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TetrisInput {
     SoftDrop,
     HardDrop,
@@ -48,25 +66,22 @@ trait Controllable: Game {
 
     fn move_left(&mut self) -> Result<(), TetrisError>;
     fn move_right(&mut self) -> Result<(), TetrisError>;
+    fn snap_left(&mut self) -> Result<(), TetrisError>;
+    fn snap_right(&mut self) -> Result<(), TetrisError>;
 
     fn hold(&mut self) -> Result<(), TetrisError>;
 
+    fn soft_drop(&mut self) -> Result<(), TetrisError>;
     fn hard_drop(&mut self) -> Result<(), TetrisError>;
 
     fn execute_input(&mut self, input: TetrisInput) -> Result<(), TetrisError> {
         match input {
-            TetrisInput::SoftDrop => {
-                todo!()
-            }
+            TetrisInput::SoftDrop => self.soft_drop(),
             TetrisInput::HardDrop => self.hard_drop(),
             TetrisInput::MoveLeft => self.move_left(),
             TetrisInput::MoveRight => self.move_right(),
-            TetrisInput::SnapRight => {
-                todo!()
-            }
-            TetrisInput::SnapLeft => {
-                todo!()
-            }
+            TetrisInput::SnapRight => self.snap_right(),
+            TetrisInput::SnapLeft => self.snap_left(),
             TetrisInput::RotateCW => self.rotate_cw(),
             TetrisInput::RotateCCW => self.rotate_ccw(),
             TetrisInput::Rotate180 => self.rotate_180(),
@@ -91,7 +106,7 @@ mod tests {
 
     #[test]
     fn game_lifetime() {
-        let mut game = TetrisGame::new_tetrio();
+        let mut game = TetrisGame::new_tetrio(None);
 
         assert_eq!(
             Err(TetrisError::GameNotStarted),
@@ -1,12 +1,16 @@
+mod kick_table;
 mod movement;
 mod piece_type;
 mod position;
 mod rotation;
+mod spin;
 mod util;
 
+pub use kick_table::{KickTable, RotationDirection};
 pub use piece_type::PieceType;
-use position::Position;
-use util::Orientation;
+pub use position::Position;
+pub use spin::{LastMove, SpinRule};
+pub use util::Orientation;
 pub use util::{DEFAULT_ORIENTATION, DEFAULT_POSITION, UNIQUE_TYPES};
 
 #[derive(Clone, Debug)]
@@ -15,6 +19,7 @@ pub struct Piece {
     mask: [u8; 4],
     orientation: Orientation,
     position: Position,
+    last_move: LastMove,
 }
 
 impl Piece {
@@ -26,6 +31,7 @@ impl Piece {
             mask: kind.mask(DEFAULT_ORIENTATION),
             orientation: DEFAULT_ORIENTATION,
             position: DEFAULT_POSITION,
+            last_move: LastMove::None,
         }
     }
 
@@ -64,4 +70,10 @@ impl Piece {
     pub fn y(&self) -> i32 {
         self.position.y()
     }
+
+    #[must_use]
+    /// Returns the last successful action performed on the piece
+    pub fn last_move(&self) -> LastMove {
+        self.last_move
+    }
 }
@@ -0,0 +1,133 @@
+use super::piece_type::PieceType;
+use super::util::Orientation;
+
+/// The direction a piece is rotated in
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RotationDirection {
+    /// A 90 degree clockwise rotation
+    Clockwise,
+    /// A 90 degree counterclockwise rotation
+    CounterClockwise,
+    /// A 180 degree rotation
+    Half,
+}
+
+/// The rotation system used to kick pieces when the rotated piece would
+/// collide with the board.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum KickTable {
+    /// The guideline Super Rotation System, which has no 180 degree kicks
+    #[default]
+    Srs,
+    /// TETR.IO's SRS+, which uses symmetric I piece kicks and adds 180 degree
+    /// kicks
+    SrsPlus,
+}
+
+/// SRS kick offsets for the J, L, S, T and Z pieces, indexed by the starting
+/// orientation and then the rotation direction (clockwise, counterclockwise).
+/// Offsets are written as in the guideline, where positive y is upwards.
+static SRS_JLSTZ_KICKS: [[[(i32, i32); 5]; 2]; 4] = [
+    [
+        [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+        [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    ],
+    [
+        [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+        [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    ],
+    [
+        [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+        [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    ],
+    [
+        [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+        [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    ],
+];
+
+/// SRS kick offsets for the I piece, laid out the same way as
+/// `SRS_JLSTZ_KICKS`.
+static SRS_I_KICKS: [[[(i32, i32); 5]; 2]; 4] = [
+    [
+        [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+        [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+    ],
+    [
+        [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+        [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+    ],
+    [
+        [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+    ],
+    [
+        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+        [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+    ],
+];
+
+/// SRS+ kick offsets for the I piece, which are mirrored between clockwise
+/// and counterclockwise rotations. Laid out the same way as
+/// `SRS_JLSTZ_KICKS`.
+static SRS_PLUS_I_KICKS: [[[(i32, i32); 5]; 2]; 4] = [
+    [
+        [(0, 0), (1, 0), (-2, 0), (-2, -1), (1, 2)],
+        [(0, 0), (-1, 0), (2, 0), (2, -1), (-1, 2)],
+    ],
+    [
+        [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+        [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)],
+    ],
+    [
+        [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+        [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)],
+    ],
+    [
+        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+        [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)],
+    ],
+];
+
+/// SRS+ 180 degree kick offsets for the J, L, S, T and Z pieces, indexed by
+/// the starting orientation. The I piece uses `SRS_PLUS_I_180_KICKS`.
+static SRS_PLUS_180_KICKS: [[(i32, i32); 6]; 4] = [
+    [(0, 0), (0, 1), (1, 1), (-1, 1), (1, 0), (-1, 0)],
+    [(0, 0), (1, 0), (1, 2), (1, 1), (0, 2), (0, 1)],
+    [(0, 0), (0, -1), (-1, -1), (1, -1), (-1, 0), (1, 0)],
+    [(0, 0), (-1, 0), (-1, 2), (-1, 1), (0, 2), (0, 1)],
+];
+
+/// SRS+ 180 degree kick offsets for the I piece, which test a single kick one
+/// cell towards the piece's new centre. Laid out the same way as
+/// `SRS_PLUS_180_KICKS`.
+static SRS_PLUS_I_180_KICKS: [[(i32, i32); 2]; 4] = [
+    [(0, 0), (0, 1)],
+    [(0, 0), (1, 0)],
+    [(0, 0), (0, -1)],
+    [(0, 0), (-1, 0)],
+];
+
+impl KickTable {
+    #[must_use]
+    /// Returns the kick offsets to test, in order, when rotating a piece of
+    /// kind `kind` in `direction` from `orientation`. Offsets are written as
+    /// in the guideline, where positive y is upwards.
+    pub fn kicks(
+        self,
+        kind: PieceType,
+        orientation: Orientation,
+        direction: RotationDirection,
+    ) -> &'static [(i32, i32)] {
+        let from = orientation as usize;
+
+        match (self, kind, direction) {
+            (_, PieceType::O, _) | (Self::Srs, _, RotationDirection::Half) => &[(0, 0)],
+            (Self::SrsPlus, PieceType::I, RotationDirection::Half) => &SRS_PLUS_I_180_KICKS[from],
+            (Self::SrsPlus, _, RotationDirection::Half) => &SRS_PLUS_180_KICKS[from],
+            (Self::Srs, PieceType::I, direction) => &SRS_I_KICKS[from][direction as usize],
+            (Self::SrsPlus, PieceType::I, direction) => &SRS_PLUS_I_KICKS[from][direction as usize],
+            (_, _, direction) => &SRS_JLSTZ_KICKS[from][direction as usize],
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::LastMove;
 use crate::{Board, Piece, TetrisError};
 
 impl Piece {
@@ -10,13 +11,14 @@ impl Piece {
             let col_to_move_to = self.x() + x_offset as i32 + 1;
 
             if col_to_move_to >= board.width() as i32
-                || board[row][col_to_move_to as usize].is_some()
+                || board[row][col_to_move_to as usize].is_filled()
             {
                 return Err(TetrisError::InvalidRightMove);
             }
         }
 
         self.position.move_right();
+        self.last_move = LastMove::Shift;
         Ok(())
     }
 
@@ -28,32 +30,88 @@ impl Piece {
             let row = self.y() as usize + y_offset;
             let curr_col = self.x() + x_offset as i32;
 
-            if curr_col <= 0 || board[row][(curr_col - 1) as usize].is_some() {
+            if curr_col <= 0 || board[row][(curr_col - 1) as usize].is_filled() {
                 return Err(TetrisError::InvalidLeftMove);
             }
         }
 
         self.position.move_left();
+        self.last_move = LastMove::Shift;
         Ok(())
     }
 
-    /// Hard drops the piece, moving it downwards until it reaches an
-    /// obstruction. The new position is calculated in constant time using
-    /// `column_heights`.
-    pub fn hard_drop(&mut self, column_heights: [u8; 10]) -> Result<(), TetrisError> {
-        let mut drop_y = 0;
+    /// Moves the piece to the right until it reaches an obstruction (an already
+    /// placed piece or the right wall), returning `Ok` if the piece moved at
+    /// least once, else a `Err(TetrisError::InvalidRightMove)`.
+    pub fn snap_right(&mut self, board: &Board) -> Result<(), TetrisError> {
+        self.move_right(board)?;
+        while self.move_right(board).is_ok() {}
 
-        for (i, y) in self.lower_edge().into_iter().flatten() {
-            // `self.x() + i` will always be positive because the rules of
-            // piece movement (no cells can be outside the board)
-            let new_drop_y = y + column_heights[(self.x() + i as i32) as usize];
+        Ok(())
+    }
 
-            if new_drop_y > drop_y {
-                drop_y = new_drop_y;
-            }
+    /// Moves the piece to the left until it reaches an obstruction (an already
+    /// placed piece or the left wall), returning `Ok` if the piece moved at
+    /// least once, else a `Err(TetrisError::InvalidLeftMove)`.
+    pub fn snap_left(&mut self, board: &Board) -> Result<(), TetrisError> {
+        self.move_left(board)?;
+        while self.move_left(board).is_ok() {}
+
+        Ok(())
+    }
+
+    /// Moves the piece down, returning `Ok` if the move is executed, else a
+    /// `Err(TetrisError::InvalidDownMove)` if the piece cannot move due to an
+    /// obstruction (an already placed piece or the floor).
+    pub fn move_down(&mut self, board: &Board) -> Result<(), TetrisError> {
+        let mut moved = self.clone();
+        moved.position.move_down();
+
+        if moved.collides(board) {
+            return Err(TetrisError::InvalidDownMove);
         }
 
-        self.position.set_y(23 - drop_y as i32);
+        self.position.move_down();
+        self.last_move = LastMove::Drop;
+        Ok(())
+    }
+
+    /// Moves the piece down until it reaches an obstruction (an already
+    /// placed piece or the floor). When every cell of the piece is above the
+    /// stack, the landing row is found from the board's column heights,
+    /// otherwise the piece is under an overhang and is moved down one row at
+    /// a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::InvalidPlacement)` if the piece is outside of
+    /// the board or overlaps an occupied cell.
+    pub fn hard_drop(&mut self, board: &Board) -> Result<(), TetrisError> {
+        if self.collides(board) {
+            return Err(TetrisError::InvalidPlacement);
+        }
+
+        let heights = board.column_heights();
+        let drop_distance = self
+            .get_pos_mask()
+            .iter()
+            .try_fold(i32::MAX, |distance, pos| {
+                let (x, _) = pos.indices()?;
+                // The row index of the column's highest filled cell, or the row
+                // below the floor if the column is empty
+                let stack_top = i32::from(board.height()) - i32::from(heights[x]);
+
+                (pos.y() < stack_top).then(|| distance.min(stack_top - 1 - pos.y()))
+            });
+
+        match drop_distance {
+            Some(0) => {}
+            Some(distance) => {
+                self.position.set_y(self.y() + distance);
+                self.last_move = LastMove::Drop;
+            }
+            None => while self.move_down(board).is_ok() {},
+        }
         Ok(())
     }
 }
@@ -221,4 +279,72 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn move_down_stops_at_obstructions() {
+        let mut board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+
+        for y in 2..=22 {
+            assert_eq!(Ok(()), piece.move_down(&board));
+            assert_eq!(
+                y,
+                piece.y(),
+                "Incorrect T piece y-position after moving down"
+            );
+        }
+        assert_eq!(
+            Err(TetrisError::InvalidDownMove),
+            piece.move_down(&board),
+            "T piece should not move down through the floor"
+        );
+
+        board.fill_cell(4, 12);
+        let mut piece = Piece::new(PieceType::T);
+        while piece.move_down(&board).is_ok() {}
+        assert_eq!(
+            10,
+            piece.y(),
+            "T piece should stop moving down when resting on a placed cell"
+        );
+    }
+
+    #[test]
+    fn snap_stops_at_obstructions() {
+        let mut board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+
+        assert_eq!(Ok(()), piece.snap_right(&board));
+        assert_eq!(7, piece.x(), "T piece should snap to the right wall");
+        assert_eq!(
+            Err(TetrisError::InvalidRightMove),
+            piece.snap_right(&board),
+            "Snapping right should fail when the piece is already at the right wall"
+        );
+
+        assert_eq!(Ok(()), piece.snap_left(&board));
+        assert_eq!(0, piece.x(), "T piece should snap to the left wall");
+        assert_eq!(
+            Err(TetrisError::InvalidLeftMove),
+            piece.snap_left(&board),
+            "Snapping left should fail when the piece is already at the left wall"
+        );
+
+        board.fill_cell(8, 2);
+        board.fill_cell(1, 2);
+        let mut piece = Piece::new(PieceType::T);
+
+        assert_eq!(Ok(()), piece.snap_right(&board));
+        assert_eq!(
+            5,
+            piece.x(),
+            "T piece should snap right until it reaches a placed cell"
+        );
+        assert_eq!(Ok(()), piece.snap_left(&board));
+        assert_eq!(
+            2,
+            piece.x(),
+            "T piece should snap left until it reaches a placed cell"
+        );
+    }
 }
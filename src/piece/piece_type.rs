@@ -53,7 +53,7 @@ impl PieceType {
                 Orientation::North => [0, 4, 5, 6],
                 Orientation::East => [1, 2, 5, 9],
                 Orientation::South => [4, 5, 6, 10],
-                Orientation::West => [1, 5, 9, 8],
+                Orientation::West => [1, 5, 8, 9],
             },
             PieceType::S => match orientation {
                 Orientation::North => [1, 2, 4, 5],
@@ -63,7 +63,7 @@ impl PieceType {
             },
             PieceType::Z => match orientation {
                 Orientation::North => [0, 1, 5, 6],
-                Orientation::East => [2, 6, 5, 9],
+                Orientation::East => [2, 5, 6, 9],
                 Orientation::South => [4, 5, 9, 10],
                 Orientation::West => [1, 4, 5, 8],
             },
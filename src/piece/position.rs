@@ -1,6 +1,6 @@
 use std::ops::{Add, Sub};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Position {
     x: i32,
     y: i32,
@@ -31,6 +31,13 @@ impl Position {
         self.y
     }
 
+    #[must_use]
+    /// Returns the position as `(column, row)` indices into the board, or
+    /// `None` if either coordinate is negative
+    pub fn indices(&self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
     /// Sets the y-coordinate of the position
     pub fn set_y(&mut self, y: i32) {
         self.y = y;
@@ -50,6 +57,11 @@ impl Position {
     pub fn move_left(&mut self) {
         self.x -= 1;
     }
+
+    /// Increments the y-coordinate of the position by 1
+    pub fn move_down(&mut self) {
+        self.y += 1;
+    }
 }
 
 impl Default for Position {
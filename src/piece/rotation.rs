@@ -1,40 +1,407 @@
-use crate::{Piece, TetrisError, piece::Position};
-
-enum RotationDirection {
-    Clockwise,
-    CounterClockwise,
-}
+use super::LastMove;
+use super::{KickTable, RotationDirection};
+use crate::{Board, Piece, TetrisError, piece::Position};
 
 impl Piece {
-    /// Rotate the piece clockwise, changing its orientation accordingly
-    pub fn rotate_cw(&mut self) -> Result<(), TetrisError> {
-        self.orientation = self.orientation.clockwise();
-        self.mask = self.kind.mask(self.orientation);
-        Ok(())
+    /// Rotates the piece clockwise, changing its orientation accordingly and
+    /// applying the first kick from `kick_table` that does not collide with
+    /// the board.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::InvalidCWRotation)` if every kick collides,
+    /// in which case the piece is left unchanged.
+    pub fn rotate_cw(&mut self, board: &Board, kick_table: KickTable) -> Result<(), TetrisError> {
+        if self.rotate(board, RotationDirection::Clockwise, kick_table) {
+            Ok(())
+        } else {
+            Err(TetrisError::InvalidCWRotation)
+        }
+    }
+
+    /// Rotates the piece counterclockwise, changing its orientation
+    /// accordingly and applying the first kick from `kick_table` that does not
+    /// collide with the board.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::InvalidCCWRotation)` if every kick collides,
+    /// in which case the piece is left unchanged.
+    pub fn rotate_ccw(&mut self, board: &Board, kick_table: KickTable) -> Result<(), TetrisError> {
+        if self.rotate(board, RotationDirection::CounterClockwise, kick_table) {
+            Ok(())
+        } else {
+            Err(TetrisError::InvalidCCWRotation)
+        }
     }
 
-    /// Rotate the piece counterclockwise, changing its orientation accordingly
-    pub fn rotate_ccw(&mut self) -> Result<(), TetrisError> {
-        self.orientation = self.orientation.counterclockwise();
-        self.mask = self.kind.mask(self.orientation);
-        Ok(())
+    /// Rotates the piece 180 degrees, changing its orientation accordingly and
+    /// applying the first kick from `kick_table` that does not collide with
+    /// the board. SRS has no 180 degree kicks, so it only tests the rotation
+    /// in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::Invalid180Rotation)` if every kick collides,
+    /// in which case the piece is left unchanged.
+    pub fn rotate_180(&mut self, board: &Board, kick_table: KickTable) -> Result<(), TetrisError> {
+        if self.rotate(board, RotationDirection::Half, kick_table) {
+            Ok(())
+        } else {
+            Err(TetrisError::Invalid180Rotation)
+        }
     }
 
-    /// Rotate the piece 180 degrees, changing its orientation accordingly
-    pub fn rotate_180(&mut self) -> Result<(), TetrisError> {
-        self.orientation = self.orientation.opposite();
-        self.mask = self.kind.mask(self.orientation);
-        Ok(())
+    /// Rotates the piece in `direction`, testing each kick offset in order and
+    /// remembering the index of the kick that succeeded. Returns `true` if a
+    /// kick succeeded, `false` otherwise.
+    fn rotate(
+        &mut self,
+        board: &Board,
+        direction: RotationDirection,
+        kick_table: KickTable,
+    ) -> bool {
+        let orientation = match direction {
+            RotationDirection::Clockwise => self.orientation.clockwise(),
+            RotationDirection::CounterClockwise => self.orientation.counterclockwise(),
+            RotationDirection::Half => self.orientation.opposite(),
+        };
+
+        let mut rotated = self.clone();
+        rotated.orientation = orientation;
+        rotated.mask = self.kind.mask(orientation);
+
+        for (kick, offset) in get_offsets(self, direction, kick_table).enumerate() {
+            rotated.position = self.position + offset;
+            rotated.last_move = LastMove::Rotation { direction, kick };
+
+            if !rotated.collides(board) {
+                *self = rotated;
+                return true;
+            }
+        }
+
+        false
     }
 }
 
-fn get_offsets(piece: &Piece, direction: RotationDirection) -> impl Iterator<Item = Position> {
-    [
-        Position::at(0, 0),
-        Position::at(0, 0),
-        Position::at(0, 0),
-        Position::at(0, 0),
-        Position::at(0, 0),
-    ]
-    .into_iter()
+/// Returns the kick offsets to test, in order, when rotating `piece` in
+/// `direction` using `kick_table`. Offsets are converted to board
+/// coordinates, where positive y is downwards.
+fn get_offsets(
+    piece: &Piece,
+    direction: RotationDirection,
+    kick_table: KickTable,
+) -> impl Iterator<Item = Position> + use<> {
+    kick_table
+        .kicks(piece.kind, piece.orientation, direction)
+        .iter()
+        .map(|&(x, y)| Position::at(x, -y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{PieceType, UNIQUE_TYPES, util::Orientation};
+    use super::*;
+
+    #[test]
+    fn empty_board_rotations_do_not_kick() {
+        for piece_type in UNIQUE_TYPES {
+            let board = Board::new();
+            let mut piece = Piece::new(piece_type);
+            let expected = [
+                Orientation::East,
+                Orientation::South,
+                Orientation::West,
+                Orientation::North,
+            ];
+
+            for orientation in expected {
+                assert_eq!(
+                    Ok(()),
+                    piece.rotate_cw(&board, KickTable::Srs),
+                    "{piece_type} piece should rotate clockwise on an empty board"
+                );
+                assert_eq!(
+                    orientation,
+                    piece.orientation(),
+                    "Incorrect {piece_type} piece orientation after rotating clockwise"
+                );
+                assert_eq!(
+                    (3, 1),
+                    (piece.x(), piece.y()),
+                    "{piece_type} piece should not be kicked when rotating \
+                    {orientation} on an empty board"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn t_kicks_off_left_wall() {
+        let board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+
+        piece.rotate_cw(&board, KickTable::Srs).unwrap();
+        while piece.move_left(&board).is_ok() {}
+        assert_eq!(-1, piece.x(), "East T piece should reach the left wall");
+
+        assert_eq!(Ok(()), piece.rotate_ccw(&board, KickTable::Srs));
+        assert_eq!(Orientation::North, piece.orientation());
+        assert_eq!(
+            (0, 1),
+            (piece.x(), piece.y()),
+            "T piece should be kicked right by the second East -> North test"
+        );
+    }
+
+    #[test]
+    fn i_kicks_off_right_wall() {
+        let board = Board::new();
+        let mut piece = Piece::new(PieceType::I);
+
+        piece.rotate_cw(&board, KickTable::Srs).unwrap();
+        while piece.move_right(&board).is_ok() {}
+        assert_eq!(7, piece.x(), "East I piece should reach the right wall");
+
+        assert_eq!(Ok(()), piece.rotate_cw(&board, KickTable::Srs));
+        assert_eq!(Orientation::South, piece.orientation());
+        assert_eq!(
+            (6, 1),
+            (piece.x(), piece.y()),
+            "I piece should be kicked left by the second East -> South test"
+        );
+    }
+
+    #[test]
+    fn t_kicks_around_stack() {
+        let mut board = Board::new();
+        board.fill_cell(4, 3);
+        let mut piece = Piece::new(PieceType::T);
+
+        assert_eq!(Ok(()), piece.rotate_cw(&board, KickTable::Srs));
+        assert_eq!(Orientation::East, piece.orientation());
+        assert_eq!(
+            (2, 1),
+            (piece.x(), piece.y()),
+            "T piece should be kicked left by the second North -> East test"
+        );
+    }
+
+    #[test]
+    fn blocked_rotations_fail() {
+        let mut board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+        let cells = piece.get_pos_mask();
+
+        for y in 0..board.height() as usize {
+            for x in 0..board.width() as usize {
+                let in_piece = cells.iter().any(|pos| {
+                    usize::try_from(pos.x()) == Ok(x) && usize::try_from(pos.y()) == Ok(y)
+                });
+                if !in_piece {
+                    board.fill_cell(x, y);
+                }
+            }
+        }
+
+        assert_eq!(
+            Err(TetrisError::InvalidCWRotation),
+            piece.rotate_cw(&board, KickTable::Srs)
+        );
+        assert_eq!(
+            Err(TetrisError::InvalidCCWRotation),
+            piece.rotate_ccw(&board, KickTable::Srs)
+        );
+        assert_eq!(
+            Err(TetrisError::Invalid180Rotation),
+            piece.rotate_180(&board, KickTable::Srs)
+        );
+        assert_eq!(
+            Orientation::North,
+            piece.orientation(),
+            "Failed rotations should leave the piece unchanged"
+        );
+        assert_eq!((3, 1), (piece.x(), piece.y()));
+    }
+
+    #[test]
+    fn srs_plus_i_kicks_are_mirrored() {
+        let mut board = Board::new();
+        board.fill_cell(5, 3);
+
+        let mut srs_piece = Piece::new(PieceType::I);
+        assert_eq!(Ok(()), srs_piece.rotate_cw(&board, KickTable::Srs));
+        assert_eq!(
+            (1, 1),
+            (srs_piece.x(), srs_piece.y()),
+            "SRS I piece should be kicked two cells left by the second North -> East test"
+        );
+
+        let mut srs_plus_piece = Piece::new(PieceType::I);
+        assert_eq!(Ok(()), srs_plus_piece.rotate_cw(&board, KickTable::SrsPlus));
+        assert_eq!(
+            (4, 1),
+            (srs_plus_piece.x(), srs_plus_piece.y()),
+            "SRS+ I piece should be kicked one cell right by the second North -> East test"
+        );
+    }
+
+    #[test]
+    fn srs_plus_i_kicks_mirror_every_orientation() {
+        let mirrored = [
+            (Orientation::North, Orientation::North),
+            (Orientation::East, Orientation::West),
+            (Orientation::South, Orientation::South),
+            (Orientation::West, Orientation::East),
+        ];
+
+        for (from, mirror) in mirrored {
+            let clockwise =
+                KickTable::SrsPlus.kicks(PieceType::I, from, RotationDirection::Clockwise);
+            let counterclockwise =
+                KickTable::SrsPlus.kicks(PieceType::I, mirror, RotationDirection::CounterClockwise);
+
+            assert!(
+                clockwise
+                    .iter()
+                    .zip(counterclockwise)
+                    .all(|(&(x, y), &(mirror_x, mirror_y))| x == -mirror_x && y == mirror_y),
+                "SRS+ I kicks clockwise from {from:?} should mirror the kicks counterclockwise \
+                from {mirror:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn srs_plus_i_kicks_from_west() {
+        /// Creates a vertical I piece facing West at (3, 10), whose cells fill
+        /// column 4 from row 10 down
+        fn west_i(board: &Board) -> Piece {
+            let mut piece = Piece::new(PieceType::I);
+            piece.rotate_ccw(board, KickTable::SrsPlus).unwrap();
+            piece.position.set_y(10);
+            piece
+        }
+
+        let mut board = Board::new();
+        board.fill_cell(1, 11);
+        board.fill_cell(5, 11);
+        let mut piece = west_i(&board);
+        assert_eq!(Ok(()), piece.rotate_cw(&board, KickTable::SrsPlus));
+        assert_eq!(
+            (4, 12),
+            (piece.x(), piece.y()),
+            "SRS+ I piece should be kicked right 1 and down 2 by the fourth West -> North test"
+        );
+
+        let mut board = Board::new();
+        board.fill_cell(1, 12);
+        board.fill_cell(5, 12);
+        let mut piece = west_i(&board);
+        assert_eq!(Ok(()), piece.rotate_ccw(&board, KickTable::SrsPlus));
+        assert_eq!(
+            (4, 8),
+            (piece.x(), piece.y()),
+            "SRS+ I piece should be kicked right 1 and up 2 by the fourth West -> South test"
+        );
+    }
+
+    #[test]
+    fn srs_plus_180_kicks_up_off_floor() {
+        let board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+        piece.position.set_y(22);
+
+        assert_eq!(
+            Err(TetrisError::Invalid180Rotation),
+            piece.rotate_180(&board, KickTable::Srs),
+            "SRS has no 180 kicks, so a T piece resting on the floor cannot rotate 180"
+        );
+
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert_eq!(Orientation::South, piece.orientation());
+        assert_eq!(
+            (3, 21),
+            (piece.x(), piece.y()),
+            "T piece should be kicked up by the second North -> South test"
+        );
+    }
+
+    #[test]
+    fn srs_plus_180_kicks_off_left_wall() {
+        let board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+
+        piece.rotate_cw(&board, KickTable::SrsPlus).unwrap();
+        while piece.move_left(&board).is_ok() {}
+
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert_eq!(Orientation::West, piece.orientation());
+        assert_eq!(
+            (0, 1),
+            (piece.x(), piece.y()),
+            "T piece should be kicked right by the second East -> West test"
+        );
+    }
+
+    #[test]
+    fn srs_plus_180_kicks_down_under_overhang() {
+        let mut board = Board::new();
+        board.fill_cell(4, 20);
+        let mut piece = Piece::new(PieceType::T);
+        piece.rotate_180(&board, KickTable::SrsPlus).unwrap();
+        piece.position.set_y(20);
+
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert_eq!(Orientation::North, piece.orientation());
+        assert_eq!(
+            (3, 21),
+            (piece.x(), piece.y()),
+            "T piece should be kicked down by the second South -> North test"
+        );
+    }
+
+    #[test]
+    fn i_180_kicks() {
+        let board = Board::new();
+
+        let mut piece = Piece::new(PieceType::I);
+        piece.position.set_y(22);
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert!(
+            piece.get_pos_mask().iter().all(|pos| pos.y() == 23),
+            "A flat I piece on the floor should be kicked up by 1 when rotating 180 degrees"
+        );
+
+        let mut piece = Piece::new(PieceType::I);
+        piece.rotate_cw(&board, KickTable::SrsPlus).unwrap();
+        while piece.move_left(&board).is_ok() {}
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert!(
+            piece.get_pos_mask().iter().all(|pos| pos.x() == 0),
+            "A vertical I piece against the left wall should be kicked right by 1 when \
+            rotating 180 degrees"
+        );
+
+        let mut piece = Piece::new(PieceType::I);
+        piece.rotate_180(&board, KickTable::SrsPlus).unwrap();
+        piece.position.set_y(-1);
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert!(
+            piece.get_pos_mask().iter().all(|pos| pos.y() == 0),
+            "A flat I piece against the ceiling should be kicked down by 1 when rotating 180 \
+            degrees"
+        );
+
+        let mut piece = Piece::new(PieceType::I);
+        piece.rotate_ccw(&board, KickTable::SrsPlus).unwrap();
+        while piece.move_right(&board).is_ok() {}
+        assert_eq!(Ok(()), piece.rotate_180(&board, KickTable::SrsPlus));
+        assert!(
+            piece.get_pos_mask().iter().all(|pos| pos.x() == 9),
+            "A vertical I piece against the right wall should be kicked left by 1 when \
+            rotating 180 degrees"
+        );
+    }
 }
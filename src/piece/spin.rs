@@ -0,0 +1,342 @@
+use super::{Orientation, PieceType, Position, RotationDirection};
+use crate::{Board, Piece, Spin};
+
+/// The kick index of the last test in the SRS clockwise and counterclockwise
+/// kick tables, the (±1, ±2) kick, which upgrades a T-spin mini to a full
+/// T-spin. This is the kick used by T-spin triples and fin setups. 180 degree
+/// rotations never upgrade a mini.
+const UPGRADE_KICK: usize = 4;
+
+/// The corners of the 3x3 box around the centre of a T piece, relative to
+/// the piece's position, in clockwise order from the top left
+const T_CORNERS: [Position; 4] = [
+    Position::at(0, 0),
+    Position::at(2, 0),
+    Position::at(2, 2),
+    Position::at(0, 2),
+];
+
+/// The directions a piece is tested in to check whether it is immobile
+const IMMOBILE_OFFSETS: [Position; 4] = [
+    Position::at(-1, 0),
+    Position::at(1, 0),
+    Position::at(0, -1),
+    Position::at(0, 1),
+];
+
+/// Which pieces can spin, and what kind of spin they are awarded
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SpinRule {
+    /// Only T pieces can spin, using the 3-corner rule
+    #[default]
+    TSpinsOnly,
+    /// T pieces spin using the 3-corner rule, and every other piece except O
+    /// is awarded a mini spin if it is immobile after rotating, as in
+    /// TETR.IO's all-mini+ rule
+    AllMini,
+    /// T pieces spin using the 3-corner rule, and every other piece except O
+    /// is awarded a full spin if it is immobile after rotating
+    AllSpin,
+}
+
+/// The last successful action performed on a piece
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum LastMove {
+    /// The piece has not moved since it spawned
+    #[default]
+    None,
+    /// The piece moved left or right
+    Shift,
+    /// The piece moved down, by gravity, soft drop or hard drop
+    Drop,
+    /// The piece rotated in `direction` using the kick at index `kick` of its
+    /// kick table, where 0 is the rotation in place
+    Rotation {
+        direction: RotationDirection,
+        kick: usize,
+    },
+}
+
+impl Piece {
+    #[must_use]
+    /// Returns the spin the piece would lock with on `board` under
+    /// `spin_rule`. Only pieces whose last move was a rotation can spin.
+    ///
+    /// A T piece spins if at least three of the corners around its centre are
+    /// occupied, counting walls and the floor. It is a full T-spin if both
+    /// corners it points towards are occupied or it rotated using the last
+    /// kick of a 90 degree rotation, otherwise a T-spin mini. Other pieces,
+    /// except O, spin under `SpinRule::AllMini` and `SpinRule::AllSpin` if
+    /// they cannot move left, right, up or down.
+    pub fn spin(&self, board: &Board, spin_rule: SpinRule) -> Spin {
+        let LastMove::Rotation { direction, kick } = self.last_move else {
+            return Spin::None;
+        };
+
+        match (self.kind, spin_rule) {
+            (PieceType::T, _) => {
+                let upgrade = direction != RotationDirection::Half && kick == UPGRADE_KICK;
+                self.t_spin(board, upgrade)
+            }
+            (PieceType::O, _) | (_, SpinRule::TSpinsOnly) => Spin::None,
+            (_, SpinRule::AllMini) if self.is_immobile(board) => Spin::Mini,
+            (_, SpinRule::AllSpin) if self.is_immobile(board) => Spin::Full,
+            _ => Spin::None,
+        }
+    }
+
+    /// Returns the T-spin the piece would lock with on `board` using the
+    /// 3-corner rule, upgrading a mini to a full T-spin if `upgrade` is `true`
+    fn t_spin(&self, board: &Board, upgrade: bool) -> Spin {
+        let occupied = T_CORNERS.map(|corner| board.is_occupied(self.position + corner));
+        let front = match self.orientation {
+            Orientation::North => [0, 1],
+            Orientation::East => [1, 2],
+            Orientation::South => [2, 3],
+            Orientation::West => [3, 0],
+        };
+
+        let corners = occupied.iter().filter(|&&occupied| occupied).count();
+        let front_corners = front.iter().filter(|&&i| occupied[i]).count();
+
+        if corners < 3 {
+            Spin::None
+        } else if front_corners == 2 || upgrade {
+            Spin::Full
+        } else {
+            Spin::Mini
+        }
+    }
+
+    /// Returns `true` if the piece collides with `board` when moved one cell
+    /// in any direction, `false` otherwise
+    fn is_immobile(&self, board: &Board) -> bool {
+        IMMOBILE_OFFSETS.iter().all(|&offset| {
+            let mut moved = self.clone();
+            moved.position = self.position + offset;
+            moved.collides(board)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KickTable;
+
+    /// Creates a T piece at `position` facing `orientation`, as though it had
+    /// just rotated clockwise into place using the kick at index `kick`
+    fn rotated_t(position: Position, orientation: Orientation, kick: usize) -> Piece {
+        let mut piece = Piece::new(PieceType::T);
+        piece.position = position;
+        piece.orientation = orientation;
+        piece.mask = PieceType::T.mask(orientation);
+        piece.last_move = LastMove::Rotation {
+            direction: RotationDirection::Clockwise,
+            kick,
+        };
+        piece
+    }
+
+    /// Fills the bottom row except for column 4 and the cell above the left
+    /// of the T slot, forming a T-spin double slot
+    fn tsd_board() -> Board {
+        let mut board = Board::new();
+        for x in (0..10).filter(|&x| x != 4) {
+            board.fill_cell(x, 23);
+        }
+        board.fill_cell(3, 21);
+        board
+    }
+
+    #[test]
+    fn moves_are_tracked() {
+        let board = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+        assert_eq!(LastMove::None, piece.last_move());
+
+        piece.move_left(&board).unwrap();
+        assert_eq!(LastMove::Shift, piece.last_move());
+
+        piece.rotate_cw(&board, KickTable::Srs).unwrap();
+        assert_eq!(
+            LastMove::Rotation {
+                direction: RotationDirection::Clockwise,
+                kick: 0,
+            },
+            piece.last_move()
+        );
+
+        piece.move_down(&board).unwrap();
+        assert_eq!(LastMove::Drop, piece.last_move());
+    }
+
+    #[test]
+    fn kick_index_is_tracked() {
+        let mut board = Board::new();
+        board.fill_cell(4, 3);
+        let mut piece = Piece::new(PieceType::T);
+
+        piece.rotate_cw(&board, KickTable::Srs).unwrap();
+        assert_eq!(
+            LastMove::Rotation {
+                direction: RotationDirection::Clockwise,
+                kick: 1,
+            },
+            piece.last_move(),
+            "T piece should remember it was kicked by the second test"
+        );
+    }
+
+    #[test]
+    fn t_spin_double() {
+        let mut board = tsd_board();
+        let piece = rotated_t(Position::at(3, 21), Orientation::South, 0);
+
+        assert_eq!(Spin::Full, piece.spin(&board, SpinRule::TSpinsOnly));
+
+        let placement = board.lock(&piece, SpinRule::TSpinsOnly).unwrap();
+        assert_eq!(
+            Spin::Full,
+            placement.spin(),
+            "Locking a T piece in a T-spin slot should report a full T-spin"
+        );
+    }
+
+    #[test]
+    fn t_spin_mini_against_wall() {
+        let mut board = Board::new();
+        board.fill_cell(0, 22);
+        let piece = rotated_t(Position::at(0, 22), Orientation::North, 0);
+
+        assert_eq!(
+            Spin::Mini,
+            piece.spin(&board, SpinRule::TSpinsOnly),
+            "Only one front corner is occupied, so the T-spin should be a mini"
+        );
+    }
+
+    #[test]
+    fn last_kick_upgrades_mini() {
+        let mut board = Board::new();
+        board.fill_cell(0, 22);
+        let piece = rotated_t(Position::at(0, 22), Orientation::North, UPGRADE_KICK);
+
+        assert_eq!(
+            Spin::Full,
+            piece.spin(&board, SpinRule::TSpinsOnly),
+            "Rotating with the last kick should upgrade a T-spin mini to a full T-spin"
+        );
+    }
+
+    #[test]
+    fn half_rotation_kick_does_not_upgrade_mini() {
+        let mut board = Board::new();
+        board.fill_cell(0, 22);
+        let mut piece = rotated_t(Position::at(0, 22), Orientation::North, UPGRADE_KICK);
+        piece.last_move = LastMove::Rotation {
+            direction: RotationDirection::Half,
+            kick: UPGRADE_KICK,
+        };
+
+        assert_eq!(
+            Spin::Mini,
+            piece.spin(&board, SpinRule::TSpinsOnly),
+            "A 180 degree rotation with its fifth kick, a sideways shift, should stay a T-spin \
+            mini"
+        );
+    }
+
+    #[test]
+    fn no_spin_without_rotation() {
+        let board = tsd_board();
+        let mut piece = rotated_t(Position::at(3, 21), Orientation::South, 0);
+        piece.last_move = LastMove::Drop;
+        assert_eq!(
+            Spin::None,
+            piece.spin(&board, SpinRule::TSpinsOnly),
+            "A T piece that moved after rotating should not spin"
+        );
+
+        let piece = rotated_t(Position::at(3, 19), Orientation::South, 0);
+        assert_eq!(
+            Spin::None,
+            piece.spin(&board, SpinRule::TSpinsOnly),
+            "A T piece with fewer than three occupied corners should not spin"
+        );
+    }
+
+    #[test]
+    fn immobile_pieces_spin() {
+        let expected = [
+            (SpinRule::TSpinsOnly, Spin::None),
+            (SpinRule::AllMini, Spin::Mini),
+            (SpinRule::AllSpin, Spin::Full),
+        ];
+
+        for (spin_rule, spin) in expected {
+            let mut board = Board::new();
+            for x in 4..10 {
+                board.fill_cell(x, 23);
+            }
+            for x in 0..4 {
+                board.fill_cell(x, 22);
+            }
+
+            let mut piece = Piece::new(PieceType::I);
+            piece.position = Position::at(0, 22);
+            piece.last_move = LastMove::Rotation {
+                direction: RotationDirection::Clockwise,
+                kick: 0,
+            };
+
+            assert_eq!(
+                spin,
+                piece.spin(&board, spin_rule),
+                "Incorrect spin for an immobile I piece with {spin_rule:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn mobile_pieces_do_not_spin() {
+        let mut board = Board::new();
+        for x in 4..10 {
+            board.fill_cell(x, 23);
+        }
+
+        let mut piece = Piece::new(PieceType::I);
+        piece.position = Position::at(0, 22);
+        piece.last_move = LastMove::Rotation {
+            direction: RotationDirection::Clockwise,
+            kick: 0,
+        };
+
+        assert_eq!(
+            Spin::None,
+            piece.spin(&board, SpinRule::AllSpin),
+            "An I piece that can move up should not spin"
+        );
+    }
+
+    #[test]
+    fn o_pieces_do_not_spin() {
+        let mut board = Board::new();
+        for x in 2..10 {
+            board.fill_cell(x, 23);
+            board.fill_cell(x, 22);
+        }
+        for x in 0..2 {
+            board.fill_cell(x, 21);
+        }
+
+        let mut piece = Piece::new(PieceType::O);
+        piece.position = Position::at(-1, 22);
+        piece.last_move = LastMove::Rotation {
+            direction: RotationDirection::Clockwise,
+            kick: 0,
+        };
+
+        assert_eq!(Spin::None, piece.spin(&board, SpinRule::AllSpin));
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::{Display, Error, Formatter};
 use super::Piece;
 use super::piece_type::PieceType;
 use super::position::Position;
+use crate::Board;
 
 pub const DEFAULT_POSITION: Position = Position::at(3, 1);
 pub const DEFAULT_ORIENTATION: Orientation = Orientation::North;
@@ -16,7 +17,7 @@ pub const UNIQUE_TYPES: [PieceType; 7] = [
     PieceType::O,
 ];
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Orientation {
     North,
     East,
@@ -84,6 +85,15 @@ impl Piece {
         pos_mask
     }
 
+    #[must_use]
+    /// Returns `true` if any of the piece's cells lie outside of the board or
+    /// overlap an occupied cell, `false` otherwise.
+    pub fn collides(&self, board: &Board) -> bool {
+        self.get_pos_mask()
+            .into_iter()
+            .any(|pos| board.is_occupied(pos))
+    }
+
     pub fn left_edge(&self) -> [Option<(usize, u8)>; 4] {
         let mut left_edge = [None; 4];
 
@@ -0,0 +1,143 @@
+mod bag_plus_one;
+mod nes;
+mod pure_random;
+mod tgm;
+mod tgm3;
+
+use bag_plus_one::BagPlusOne;
+use nes::Nes;
+use pure_random::PureRandom;
+use tgm::Tgm;
+use tgm3::Tgm3;
+
+use crate::{Bag, PieceType};
+
+/// A generator of the pieces that fill a game's piece queue. Randomizers are
+/// `Send` so that games using them can run on other threads.
+pub trait Randomizer: Send {
+    /// Returns the next piece
+    fn draw(&mut self) -> PieceType;
+}
+
+/// The built-in randomizers a game can draw its pieces from
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum RandomizerKind {
+    /// Shuffles bags of `GameConfig::bag_size` pieces, such as the 7-bag and
+    /// 14-bag, matching TETR.IO's bags for the same seed
+    #[default]
+    Bag,
+    /// Shuffles bags of all 7 pieces plus one random extra piece
+    BagPlusOne,
+    /// Draws every piece independently at random
+    Random,
+    /// The NES randomizer, which rerolls once if it draws the previous piece
+    Nes,
+    /// The TGM randomizer, which rerolls up to 4 times if it draws one of the
+    /// last 4 pieces
+    Tgm,
+    /// The TGM2 randomizer, which rerolls up to 6 times if it draws one of
+    /// the last 4 pieces
+    Tgm2,
+    /// The TGM3 randomizer, which draws from a pool of 35 pieces that favours
+    /// pieces that have not been seen for the longest
+    Tgm3,
+}
+
+impl RandomizerKind {
+    #[must_use]
+    /// Creates a randomizer of this kind, seeded with `seed`. `bag_size` is
+    /// only used by `RandomizerKind::Bag`.
+    pub fn build(self, bag_size: u32, seed: u64) -> Box<dyn Randomizer> {
+        match self {
+            RandomizerKind::Bag => Box::new(Bag::new(bag_size, seed)),
+            RandomizerKind::BagPlusOne => Box::new(BagPlusOne::new(seed)),
+            RandomizerKind::Random => Box::new(PureRandom::new(seed)),
+            RandomizerKind::Nes => Box::new(Nes::new(seed)),
+            RandomizerKind::Tgm => Box::new(Tgm::tgm1(seed)),
+            RandomizerKind::Tgm2 => Box::new(Tgm::tgm2(seed)),
+            RandomizerKind::Tgm3 => Box::new(Tgm3::new(seed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::piece::UNIQUE_TYPES;
+    use crate::{BattleRoyale, GameConfig, TetrisGame, VersusMatch};
+
+    #[test]
+    fn seeded_randomizers_are_deterministic() {
+        let kinds = [
+            RandomizerKind::Bag,
+            RandomizerKind::BagPlusOne,
+            RandomizerKind::Random,
+            RandomizerKind::Nes,
+            RandomizerKind::Tgm,
+            RandomizerKind::Tgm2,
+            RandomizerKind::Tgm3,
+        ];
+
+        for kind in kinds {
+            let draw = |seed| {
+                let mut randomizer = kind.build(7, seed);
+                (0..70).map(|_| randomizer.draw()).collect::<Vec<_>>()
+            };
+
+            let pieces = draw(3);
+            assert_eq!(pieces, draw(3), "{kind:?} should be deterministic");
+            assert!(
+                UNIQUE_TYPES.iter().all(|kind| pieces.contains(kind)),
+                "{kind:?} should draw every piece in 70 draws"
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_randomizers_are_stable() {
+        let expected = [
+            (RandomizerKind::Bag, "LOSTIJZLOZTISJ"),
+            (RandomizerKind::BagPlusOne, "SLJZTIIOZIOSTL"),
+            (RandomizerKind::Random, "ITOIIITZOTJLLT"),
+            (RandomizerKind::Nes, "IOIIOZTJLJOLJI"),
+            (RandomizerKind::Tgm, "ITOTZJLOILOJZS"),
+            (RandomizerKind::Tgm2, "ITOOJLTIOJZSLO"),
+            (RandomizerKind::Tgm3, "ILTJOSZTIJLOSI"),
+        ];
+
+        for (kind, pieces) in expected {
+            let mut randomizer = kind.build(7, 12345);
+            let drawn: String = (0..14).map(|_| randomizer.draw().to_string()).collect();
+            assert_eq!(
+                pieces, drawn,
+                "{kind:?} should draw the same pieces for a seed between versions"
+            );
+        }
+    }
+
+    #[test]
+    fn games_are_send() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<TetrisGame>();
+        assert_send::<VersusMatch>();
+        assert_send::<BattleRoyale>();
+    }
+
+    #[test]
+    fn games_use_custom_randomizers() {
+        struct OnlyT;
+        impl Randomizer for OnlyT {
+            fn draw(&mut self) -> PieceType {
+                PieceType::T
+            }
+        }
+
+        let mut game = TetrisGame::with_randomizer(GameConfig::default(), Box::new(OnlyT));
+        game.fill_queue();
+        assert!(
+            game.piece_queue().iter().all(|&kind| kind == PieceType::T),
+            "The queue should be drawn from the game's randomizer"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use super::Randomizer;
+use crate::piece::UNIQUE_TYPES;
+use crate::{ParkMiller, PieceType};
+
+use std::collections::VecDeque;
+
+/// A randomizer that shuffles bags of all 7 pieces plus one random extra
+/// piece
+pub struct BagPlusOne {
+    rng: ParkMiller,
+    pieces: VecDeque<PieceType>,
+}
+
+impl BagPlusOne {
+    #[must_use]
+    /// Creates a randomizer seeded with `seed`
+    pub fn new(seed: u64) -> BagPlusOne {
+        BagPlusOne {
+            rng: ParkMiller::new(seed),
+            pieces: VecDeque::new(),
+        }
+    }
+}
+
+impl Randomizer for BagPlusOne {
+    fn draw(&mut self) -> PieceType {
+        if self.pieces.is_empty() {
+            let extra = UNIQUE_TYPES[self.rng.next_index(UNIQUE_TYPES.len())];
+            let mut bag = [UNIQUE_TYPES.as_slice(), &[extra]].concat();
+            self.rng.shuffle(&mut bag);
+            self.pieces.extend(bag);
+        }
+
+        self.pieces
+            .pop_front()
+            .expect("A filled bag should contain at least one piece")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bags_contain_every_piece() {
+        let mut randomizer = BagPlusOne::new(0);
+        for _ in 0..10 {
+            let bag: Vec<PieceType> = (0..8).map(|_| randomizer.draw()).collect();
+            assert!(
+                UNIQUE_TYPES.iter().all(|kind| bag.contains(kind)),
+                "Every bag of 8 should contain all 7 pieces, but was {bag:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use super::Randomizer;
+use crate::piece::UNIQUE_TYPES;
+use crate::{ParkMiller, PieceType};
+
+/// The NES randomizer, which rolls one of 8 outcomes, and rolls once more
+/// between the 7 pieces if the first roll was the eighth outcome or the
+/// previous piece
+pub struct Nes {
+    rng: ParkMiller,
+    previous: Option<PieceType>,
+}
+
+impl Nes {
+    #[must_use]
+    /// Creates a randomizer seeded with `seed`
+    pub fn new(seed: u64) -> Nes {
+        Nes {
+            rng: ParkMiller::new(seed),
+            previous: None,
+        }
+    }
+}
+
+impl Randomizer for Nes {
+    fn draw(&mut self) -> PieceType {
+        let roll = UNIQUE_TYPES.get(self.rng.next_index(UNIQUE_TYPES.len() + 1));
+
+        let piece = match roll {
+            Some(&piece) if Some(piece) != self.previous => piece,
+            _ => UNIQUE_TYPES[self.rng.next_index(UNIQUE_TYPES.len())],
+        };
+        self.previous = Some(piece);
+        piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_are_rare() {
+        let mut nes = Nes::new(0);
+        let pieces: Vec<PieceType> = (0..7000).map(|_| nes.draw()).collect();
+        let repeats = pieces.windows(2).filter(|pair| pair[0] == pair[1]).count();
+
+        assert!(
+            repeats < 500,
+            "About 1 in 28 pieces should repeat the previous piece, but {repeats} of 7000 did"
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use super::Randomizer;
+use crate::piece::UNIQUE_TYPES;
+use crate::{ParkMiller, PieceType};
+
+/// A randomizer that draws every piece independently at random
+pub struct PureRandom {
+    rng: ParkMiller,
+}
+
+impl PureRandom {
+    #[must_use]
+    /// Creates a randomizer seeded with `seed`
+    pub fn new(seed: u64) -> PureRandom {
+        PureRandom {
+            rng: ParkMiller::new(seed),
+        }
+    }
+}
+
+impl Randomizer for PureRandom {
+    fn draw(&mut self) -> PieceType {
+        UNIQUE_TYPES[self.rng.next_index(UNIQUE_TYPES.len())]
+    }
+}
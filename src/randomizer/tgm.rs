@@ -0,0 +1,108 @@
+use super::Randomizer;
+use crate::piece::UNIQUE_TYPES;
+use crate::{ParkMiller, PieceType};
+
+use std::collections::VecDeque;
+
+/// The pieces the first piece of a TGM game can be, which exclude the S, Z
+/// and O pieces
+pub(super) const FIRST_PIECES: [PieceType; 4] =
+    [PieceType::I, PieceType::J, PieceType::L, PieceType::T];
+
+/// The TGM randomizer, which rerolls a limited number of times if it draws
+/// one of the last 4 pieces
+pub struct Tgm {
+    rng: ParkMiller,
+    history: VecDeque<PieceType>,
+    rolls: u32,
+    first: bool,
+}
+
+impl Tgm {
+    #[must_use]
+    /// Creates the TGM randomizer, which rolls up to 4 times with a history
+    /// starting as Z, Z, Z, Z, seeded with `seed`
+    pub fn tgm1(seed: u64) -> Tgm {
+        Self::new(4, [PieceType::Z; 4], seed)
+    }
+
+    #[must_use]
+    /// Creates the TGM2 randomizer, which rolls up to 6 times with a history
+    /// starting as Z, S, S, Z, seeded with `seed`
+    pub fn tgm2(seed: u64) -> Tgm {
+        Self::new(
+            6,
+            [PieceType::Z, PieceType::S, PieceType::S, PieceType::Z],
+            seed,
+        )
+    }
+
+    /// Creates a randomizer that rolls up to `rolls` times with a history
+    /// starting as `history`, seeded with `seed`
+    fn new(rolls: u32, history: [PieceType; 4], seed: u64) -> Tgm {
+        Tgm {
+            rng: ParkMiller::new(seed),
+            history: VecDeque::from(history),
+            rolls,
+            first: true,
+        }
+    }
+
+    /// Returns a random piece
+    fn roll(&mut self) -> PieceType {
+        UNIQUE_TYPES[self.rng.next_index(UNIQUE_TYPES.len())]
+    }
+}
+
+impl Randomizer for Tgm {
+    fn draw(&mut self) -> PieceType {
+        let piece = if self.first {
+            self.first = false;
+            FIRST_PIECES[self.rng.next_index(FIRST_PIECES.len())]
+        } else {
+            let mut piece = self.roll();
+            for _ in 1..self.rolls {
+                if !self.history.contains(&piece) {
+                    break;
+                }
+                piece = self.roll();
+            }
+            piece
+        };
+
+        self.history.pop_front();
+        self.history.push_back(piece);
+        piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_piece_is_not_s_z_or_o() {
+        for seed in 0..100 {
+            assert!(
+                FIRST_PIECES.contains(&Tgm::tgm1(seed).draw()),
+                "The first piece should never be an S, Z or O piece"
+            );
+        }
+    }
+
+    #[test]
+    fn history_prevents_repeats() {
+        let repeats = |mut tgm: Tgm| {
+            let pieces: Vec<PieceType> = (0..7000).map(|_| tgm.draw()).collect();
+            pieces.windows(2).filter(|pair| pair[0] == pair[1]).count()
+        };
+
+        let tgm = repeats(Tgm::tgm1(0));
+        let tgm2 = repeats(Tgm::tgm2(0));
+        assert!(
+            tgm2 < tgm && tgm < 350,
+            "More rolls should repeat pieces less often, but TGM repeated {tgm} and TGM2 \
+            repeated {tgm2} of 7000 pieces"
+        );
+    }
+}
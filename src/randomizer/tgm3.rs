@@ -0,0 +1,121 @@
+use super::Randomizer;
+use super::tgm::FIRST_PIECES;
+use crate::piece::UNIQUE_TYPES;
+use crate::{ParkMiller, PieceType};
+
+use std::collections::VecDeque;
+
+/// The number of copies of each piece in the pool
+const POOL_COPIES: usize = 5;
+/// The most times a piece is rolled from the pool
+const ROLLS: u32 = 6;
+
+/// The TGM3 randomizer, which rolls from a pool of 35 pieces, rerolling up to
+/// 6 times if it draws one of the last 4 pieces. Every piece drawn is replaced
+/// in the pool by the piece that has not been drawn for the longest, which
+/// keeps droughts short.
+pub struct Tgm3 {
+    rng: ParkMiller,
+    pool: Vec<PieceType>,
+    history: VecDeque<PieceType>,
+    droughts: Vec<PieceType>,
+    first: bool,
+}
+
+impl Tgm3 {
+    #[must_use]
+    /// Creates a randomizer with a history starting as S, Z, S, Z, seeded
+    /// with `seed`
+    pub fn new(seed: u64) -> Tgm3 {
+        Tgm3 {
+            rng: ParkMiller::new(seed),
+            pool: UNIQUE_TYPES.repeat(POOL_COPIES),
+            history: VecDeque::from([PieceType::S, PieceType::Z, PieceType::S, PieceType::Z]),
+            droughts: Vec::new(),
+            first: true,
+        }
+    }
+
+    /// Returns the piece that has not been drawn for the longest, or `None`
+    /// if no piece has been drawn yet
+    fn most_droughted(&self) -> Option<PieceType> {
+        UNIQUE_TYPES
+            .into_iter()
+            .find(|kind| !self.droughts.contains(kind))
+            .or_else(|| self.droughts.first().copied())
+    }
+
+    /// Records `piece` as the piece drawn most recently
+    fn record_drought(&mut self, piece: PieceType) {
+        self.droughts.retain(|&kind| kind != piece);
+        self.droughts.push(piece);
+    }
+}
+
+impl Randomizer for Tgm3 {
+    fn draw(&mut self) -> PieceType {
+        let piece = if self.first {
+            self.first = false;
+            FIRST_PIECES[self.rng.next_index(FIRST_PIECES.len())]
+        } else {
+            let mut index = self.rng.next_index(self.pool.len());
+            for _ in 1..ROLLS {
+                if !self.history.contains(&self.pool[index]) {
+                    break;
+                }
+                if let Some(kind) = self.most_droughted() {
+                    self.pool[index] = kind;
+                }
+                index = self.rng.next_index(self.pool.len());
+            }
+
+            let piece = self.pool[index];
+            self.record_drought(piece);
+            if let Some(kind) = self.most_droughted() {
+                self.pool[index] = kind;
+            }
+            piece
+        };
+
+        if self.droughts.last() != Some(&piece) {
+            self.record_drought(piece);
+        }
+        self.history.pop_front();
+        self.history.push_back(piece);
+        piece
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn droughts_are_short() {
+        for seed in 0..10 {
+            let mut tgm3 = Tgm3::new(seed);
+            let mut last_seen = [0; 7];
+            for draw in 1..=700 {
+                let piece = tgm3.draw();
+                let index = UNIQUE_TYPES.iter().position(|&kind| kind == piece).unwrap();
+                last_seen[index] = draw;
+
+                for seen in last_seen {
+                    assert!(
+                        draw - seen < 20,
+                        "No piece should go 20 draws without appearing"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pool_keeps_its_size() {
+        let mut tgm3 = Tgm3::new(0);
+        for _ in 0..100 {
+            tgm3.draw();
+        }
+        assert_eq!(35, tgm3.pool.len());
+    }
+}
@@ -0,0 +1,610 @@
+use crate::{Game, GameConfig, MatchOutcome, ParkMiller, TetrisError, TetrisGame, TetrisInput};
+
+use std::collections::VecDeque;
+
+/// The badge counts at which a player's attack bonus increases, with the
+/// bonus in quarters of their attack
+const BADGE_BONUSES: [(u32, u32); 4] = [(2, 1), (6, 2), (14, 3), (30, 4)];
+
+/// How a player chooses which opponent to attack
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Targeting {
+    /// Attacks a random opponent until they are knocked out
+    #[default]
+    Random,
+    /// Attacks the opponent targeted by the fewest other players
+    Even,
+    /// Attacks the opponent with the highest stack, who is closest to being
+    /// knocked out
+    Kos,
+    /// Attacks every opponent targeting the player, or a random opponent if
+    /// nobody is
+    Attackers,
+    /// Attacks the player at the contained index, or a random opponent once
+    /// they are knocked out
+    Manual(usize),
+}
+
+/// A player in a battle royale and their standing in it
+struct Entrant {
+    game: TetrisGame,
+    targeting: Targeting,
+    target: Option<usize>,
+    last_attacker: Option<usize>,
+    kos: u32,
+    badges: u32,
+    placement: Option<usize>,
+}
+
+impl Entrant {
+    fn alive(&self) -> bool {
+        self.placement.is_none()
+    }
+}
+
+/// A match between any number of players, whose games advance in lockstep
+/// and send their attacks to the opponents they target. Knocking out a player
+/// earns their badges, which increase the attack of the player who knocked
+/// them out. Targeting is random but deterministic given the match's seed.
+pub struct BattleRoyale {
+    entrants: Vec<Entrant>,
+    rng: ParkMiller,
+    outcome: Option<MatchOutcome>,
+    frame: usize,
+}
+
+impl BattleRoyale {
+    #[must_use]
+    /// Creates a match between the players of `games`, choosing random
+    /// targets using `seed`
+    pub fn new(games: Vec<TetrisGame>, seed: u64) -> BattleRoyale {
+        BattleRoyale {
+            entrants: games
+                .into_iter()
+                .map(|game| Entrant {
+                    game,
+                    targeting: Targeting::default(),
+                    target: None,
+                    last_attacker: None,
+                    kos: 0,
+                    badges: 0,
+                    placement: None,
+                })
+                .collect(),
+            rng: ParkMiller::new(seed),
+            outcome: None,
+            frame: 0,
+        }
+    }
+
+    #[must_use]
+    /// Creates a match between `players` players who play with the rules in
+    /// `config`. Every player's game is seeded from `seed`, so the whole
+    /// match is deterministic given the players' inputs.
+    pub fn with_config(config: &GameConfig, players: usize, seed: u64) -> BattleRoyale {
+        let games = (0..players)
+            .map(|player| {
+                TetrisGame::with_config(GameConfig {
+                    seed: Some(seed.wrapping_add(player as u64 + 1)),
+                    ..config.clone()
+                })
+            })
+            .collect();
+
+        Self::new(games, seed)
+    }
+
+    /// Starts every player's game and chooses their first targets
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::GameOver)` if any game has already ended
+    pub fn start(&mut self) -> Result<(), TetrisError> {
+        for entrant in &mut self.entrants {
+            entrant.game.start()?;
+        }
+        self.retarget();
+        Ok(())
+    }
+
+    /// Advances every remaining player's game by one frame, executing the
+    /// inputs at the player's index in `inputs`. Then sends each player's
+    /// attacks to their targets, knocks out players whose games have ended
+    /// and chooses new targets. The match ends once at most one player
+    /// remains. Every remaining game always advances, even if another returns
+    /// an error, so they stay in lockstep.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::GameOver)` if the match has already ended,
+    /// or the first error of `Game::next_frame`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` does not have exactly one entry per player
+    pub fn next_frame(&mut self, inputs: &mut [VecDeque<TetrisInput>]) -> Result<(), TetrisError> {
+        assert_eq!(
+            self.entrants.len(),
+            inputs.len(),
+            "Expected inputs for {} players, but got inputs for {}",
+            self.entrants.len(),
+            inputs.len(),
+        );
+        if self.outcome.is_some() {
+            return Err(TetrisError::GameOver);
+        }
+
+        let results: Vec<Result<(), TetrisError>> = self
+            .entrants
+            .iter_mut()
+            .zip(inputs)
+            .filter(|(entrant, _)| entrant.alive())
+            .map(|(entrant, inputs)| entrant.game.next_frame(inputs))
+            .collect();
+
+        self.send_attacks();
+        self.knock_out();
+        self.retarget();
+
+        self.frame += 1;
+        results.into_iter().collect()
+    }
+
+    /// Sets how the player at index `player` chooses their targets
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    pub fn set_targeting(&mut self, player: usize, targeting: Targeting) {
+        self.entrants[player].targeting = targeting;
+        if self.entrants[player].alive() {
+            self.entrants[player].target = self.choose_target(player);
+        }
+    }
+
+    /// Returns the number of players in the match
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.entrants.len()
+    }
+
+    /// Returns a reference to the game of the player at index `player`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    #[must_use]
+    pub fn game(&self, player: usize) -> &TetrisGame {
+        &self.entrants[player].game
+    }
+
+    /// Returns how the player at index `player` chooses their targets
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    #[must_use]
+    pub fn targeting(&self, player: usize) -> Targeting {
+        self.entrants[player].targeting
+    }
+
+    /// Returns the index of the opponent the player at index `player` is
+    /// targeting, or `None` if they have been knocked out
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    #[must_use]
+    pub fn target(&self, player: usize) -> Option<usize> {
+        self.entrants[player].target
+    }
+
+    /// Returns the number of opponents the player at index `player` has
+    /// knocked out
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    #[must_use]
+    pub fn kos(&self, player: usize) -> u32 {
+        self.entrants[player].kos
+    }
+
+    /// Returns the number of badges the player at index `player` has earned
+    /// by knocking out opponents, including the badges of those opponents
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    #[must_use]
+    pub fn badges(&self, player: usize) -> u32 {
+        self.entrants[player].badges
+    }
+
+    /// Returns the final placement of the player at index `player`, where 1
+    /// is the winner, or `None` if they are still playing. Players knocked
+    /// out on the same frame share the best of their placements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no player at index `player`
+    #[must_use]
+    pub fn placement(&self, player: usize) -> Option<usize> {
+        self.entrants[player].placement
+    }
+
+    /// Returns the outcome of the match, or `None` if it is still running
+    #[must_use]
+    pub fn outcome(&self) -> Option<MatchOutcome> {
+        self.outcome
+    }
+
+    /// Returns the number of frames the match has run for
+    #[must_use]
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Sends each player's attacks, increased by their badges, to their
+    /// target, or to every opponent targeting them if they target attackers
+    fn send_attacks(&mut self) {
+        for player in 0..self.entrants.len() {
+            let attacks = self.entrants[player].game.take_outgoing_attacks();
+            if attacks.is_empty() {
+                continue;
+            }
+
+            let attackers = self.attackers(player);
+            let recipients = match self.entrants[player].targeting {
+                Targeting::Attackers if !attackers.is_empty() => attackers,
+                _ => self.entrants[player].target.into_iter().collect(),
+            };
+            let bonus = badge_bonus(self.entrants[player].badges);
+
+            for attack in attacks {
+                let lines = attack * (4 + bonus) / 4;
+                for &recipient in &recipients {
+                    self.entrants[recipient].game.receive_garbage(lines);
+                    self.entrants[recipient].last_attacker = Some(player);
+                }
+            }
+        }
+    }
+
+    /// Knocks out every remaining player whose game has ended, giving their
+    /// badges to the last player who attacked them, then ends the match if
+    /// at most one player remains
+    fn knock_out(&mut self) {
+        let remaining = self
+            .entrants
+            .iter()
+            .filter(|entrant| entrant.alive())
+            .count();
+        let knocked_out: Vec<usize> = (0..self.entrants.len())
+            .filter(|&player| {
+                self.entrants[player].alive() && self.entrants[player].game.game_over()
+            })
+            .collect();
+        let placement = remaining - knocked_out.len() + 1;
+
+        for &player in &knocked_out {
+            self.entrants[player].placement = Some(placement);
+            self.entrants[player].target = None;
+
+            if let Some(attacker) = self.entrants[player].last_attacker {
+                let badges = self.entrants[player].badges + 1;
+                self.entrants[attacker].kos += 1;
+                self.entrants[attacker].badges += badges;
+            }
+        }
+
+        let alive: Vec<usize> = (0..self.entrants.len())
+            .filter(|&player| self.entrants[player].alive())
+            .collect();
+        self.outcome = match alive[..] {
+            [] => Some(MatchOutcome::Draw),
+            [winner] => {
+                self.entrants[winner].placement = Some(1);
+                self.entrants[winner].target = None;
+                Some(MatchOutcome::Winner(winner))
+            }
+            _ => None,
+        };
+    }
+
+    /// Chooses new targets for every remaining player
+    fn retarget(&mut self) {
+        for player in 0..self.entrants.len() {
+            if self.entrants[player].alive() {
+                self.entrants[player].target = self.choose_target(player);
+            }
+        }
+    }
+
+    /// Returns the target of the player at index `player` according to their
+    /// targeting, or `None` if they have no opponents left
+    fn choose_target(&mut self, player: usize) -> Option<usize> {
+        let opponents: Vec<usize> = (0..self.entrants.len())
+            .filter(|&opponent| opponent != player && self.entrants[opponent].alive())
+            .collect();
+        if opponents.is_empty() {
+            return None;
+        }
+
+        let current = self.entrants[player]
+            .target
+            .filter(|target| opponents.contains(target));
+
+        let candidates = match self.entrants[player].targeting {
+            Targeting::Manual(target) if opponents.contains(&target) => vec![target],
+            Targeting::Even => {
+                let targeted_by = |opponent: usize| {
+                    self.entrants
+                        .iter()
+                        .enumerate()
+                        .filter(|&(other, entrant)| {
+                            other != player && entrant.alive() && entrant.target == Some(opponent)
+                        })
+                        .count()
+                };
+                fewest_by_key(&opponents, targeted_by)
+            }
+            Targeting::Kos => fewest_by_key(&opponents, |opponent| {
+                let heights = self.entrants[opponent].game.board().column_heights();
+                u8::MAX - heights.into_iter().max().unwrap_or(0)
+            }),
+            Targeting::Attackers => {
+                let attackers = self.attackers(player);
+                match current {
+                    Some(target) if attackers.contains(&target) => vec![target],
+                    _ if !attackers.is_empty() => attackers,
+                    Some(target) => vec![target],
+                    None => opponents,
+                }
+            }
+            Targeting::Random | Targeting::Manual(_) => match current {
+                Some(target) => vec![target],
+                None => opponents,
+            },
+        };
+
+        let choice = self.rng.next_index(candidates.len());
+        Some(candidates[choice])
+    }
+
+    /// Returns the remaining opponents targeting the player at index `player`
+    fn attackers(&self, player: usize) -> Vec<usize> {
+        (0..self.entrants.len())
+            .filter(|&other| {
+                other != player
+                    && self.entrants[other].alive()
+                    && self.entrants[other].target == Some(player)
+            })
+            .collect()
+    }
+}
+
+/// Returns the attack bonus earned by `badges` badges, in quarters of the
+/// player's attack
+fn badge_bonus(badges: u32) -> u32 {
+    BADGE_BONUSES
+        .iter()
+        .rev()
+        .find(|&&(min_badges, _)| badges >= min_badges)
+        .map_or(0, |&(_, bonus)| bonus)
+}
+
+/// Returns the players in `players` with the smallest value of `key`
+fn fewest_by_key<K: Ord>(players: &[usize], key: impl Fn(usize) -> K) -> Vec<usize> {
+    let keys: Vec<K> = players.iter().map(|&player| key(player)).collect();
+    let Some(fewest) = keys.iter().min() else {
+        return Vec::new();
+    };
+
+    players
+        .iter()
+        .zip(&keys)
+        .filter(|&(_, key)| key == fewest)
+        .map(|(&player, _)| player)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a started match between `players` players with the default
+    /// rules
+    fn started_royale(players: usize, seed: u64) -> BattleRoyale {
+        let mut royale = BattleRoyale::with_config(&GameConfig::default(), players, seed);
+        royale.start().unwrap();
+        royale
+    }
+
+    /// Returns empty inputs for every player of `royale`
+    fn no_inputs(royale: &BattleRoyale) -> Vec<VecDeque<TetrisInput>> {
+        vec![VecDeque::new(); royale.player_count()]
+    }
+
+    /// Makes the player at index `player` send a quad, which attacks for 4
+    fn send_quad(royale: &mut BattleRoyale, player: usize) {
+        royale.entrants[player].game.fill_well(19);
+        royale.entrants[player].game.drop_i_in_well();
+    }
+
+    /// Makes the players at the indices in `players` forfeit on the next
+    /// frame
+    fn forfeit(royale: &mut BattleRoyale, players: &[usize]) {
+        let mut inputs = no_inputs(royale);
+        for &player in players {
+            inputs[player].push_back(TetrisInput::Forfeit);
+        }
+        royale.next_frame(&mut inputs).unwrap();
+    }
+
+    #[test]
+    fn manual_targeting() {
+        let mut royale = started_royale(4, 0);
+        royale.set_targeting(0, Targeting::Manual(2));
+        assert_eq!(Some(2), royale.target(0));
+
+        send_quad(&mut royale, 0);
+        royale.next_frame(&mut no_inputs(&royale)).unwrap();
+
+        let pending: Vec<u32> = (0..4)
+            .map(|player| royale.game(player).pending_garbage())
+            .collect();
+        assert_eq!(
+            vec![0, 0, 4, 0],
+            pending,
+            "Attacks should only be sent to the manually chosen target"
+        );
+    }
+
+    #[test]
+    fn attackers_targeting() {
+        let mut royale = started_royale(4, 0);
+        royale.set_targeting(1, Targeting::Manual(0));
+        royale.set_targeting(3, Targeting::Manual(0));
+        royale.set_targeting(2, Targeting::Manual(1));
+        royale.set_targeting(0, Targeting::Attackers);
+
+        send_quad(&mut royale, 0);
+        royale.next_frame(&mut no_inputs(&royale)).unwrap();
+
+        let pending: Vec<u32> = (0..4)
+            .map(|player| royale.game(player).pending_garbage())
+            .collect();
+        assert_eq!(
+            vec![0, 4, 0, 4],
+            pending,
+            "Attacks should be sent to every player targeting the attacker"
+        );
+    }
+
+    #[test]
+    fn even_targeting() {
+        let mut royale = started_royale(4, 0);
+        royale.set_targeting(1, Targeting::Manual(3));
+        royale.set_targeting(2, Targeting::Manual(3));
+        royale.set_targeting(3, Targeting::Manual(1));
+        royale.set_targeting(0, Targeting::Even);
+
+        assert_eq!(
+            Some(2),
+            royale.target(0),
+            "Even targeting should choose the opponent targeted by the fewest players"
+        );
+    }
+
+    #[test]
+    fn kos_targeting() {
+        let mut royale = started_royale(4, 0);
+        royale.entrants[3].game.fill_well(10);
+        royale.set_targeting(0, Targeting::Kos);
+
+        assert_eq!(
+            Some(3),
+            royale.target(0),
+            "KOs targeting should choose the opponent with the highest stack"
+        );
+    }
+
+    #[test]
+    fn knock_outs_and_placements() {
+        let mut royale = started_royale(3, 0);
+        royale.set_targeting(0, Targeting::Manual(1));
+        send_quad(&mut royale, 0);
+        royale.next_frame(&mut no_inputs(&royale)).unwrap();
+
+        forfeit(&mut royale, &[1]);
+        assert_eq!(Some(3), royale.placement(1));
+        assert_eq!(
+            (1, 1),
+            (royale.kos(0), royale.badges(0)),
+            "The last player to attack a knocked out player should be credited with the KO"
+        );
+        assert_eq!(
+            Some(2),
+            royale.target(0),
+            "Players should retarget once their target is knocked out"
+        );
+        assert_eq!(None, royale.outcome());
+
+        forfeit(&mut royale, &[2]);
+        assert_eq!(Some(MatchOutcome::Winner(0)), royale.outcome());
+        assert_eq!(
+            [Some(1), Some(3), Some(2)],
+            [
+                royale.placement(0),
+                royale.placement(1),
+                royale.placement(2)
+            ]
+        );
+        assert_eq!(
+            Err(TetrisError::GameOver),
+            royale.next_frame(&mut no_inputs(&royale))
+        );
+    }
+
+    #[test]
+    fn simultaneous_knock_outs_share_placement() {
+        let mut royale = started_royale(4, 0);
+        forfeit(&mut royale, &[1, 2]);
+        assert_eq!(
+            [Some(3), Some(3)],
+            [royale.placement(1), royale.placement(2)]
+        );
+
+        forfeit(&mut royale, &[0, 3]);
+        assert_eq!(Some(MatchOutcome::Draw), royale.outcome());
+        assert_eq!(
+            [Some(1), Some(1)],
+            [royale.placement(0), royale.placement(3)]
+        );
+    }
+
+    #[test]
+    fn badges_increase_attack() {
+        assert_eq!(
+            [0, 0, 1, 1, 2, 3, 4],
+            [0, 1, 2, 5, 6, 14, 30].map(badge_bonus)
+        );
+
+        let mut royale = started_royale(3, 0);
+        royale.entrants[0].badges = 6;
+        royale.set_targeting(0, Targeting::Manual(1));
+        send_quad(&mut royale, 0);
+        royale.next_frame(&mut no_inputs(&royale)).unwrap();
+
+        assert_eq!(
+            6,
+            royale.game(1).pending_garbage(),
+            "A quad with 6 badges should send 4 * 1.5 lines"
+        );
+    }
+
+    #[test]
+    fn seeded_targeting_is_deterministic() {
+        let targets = |seed| {
+            let mut royale = started_royale(8, seed);
+            let mut targets = Vec::new();
+            for frame in 0..4 {
+                forfeit(&mut royale, &[frame]);
+                targets.extend((0..8).map(|player| royale.target(player)));
+            }
+            targets
+        };
+
+        assert_eq!(targets(7), targets(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected inputs for 3 players")]
+    fn missing_inputs_panic() {
+        let mut royale = started_royale(3, 0);
+        royale
+            .next_frame(&mut [VecDeque::new(), VecDeque::new()])
+            .unwrap();
+    }
+}
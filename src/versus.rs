@@ -0,0 +1,221 @@
+use crate::{Game, GameConfig, KeyEvent, TetrisError, TetrisGame, TetrisInput};
+
+use std::collections::VecDeque;
+
+/// The outcome of a finished match
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MatchOutcome {
+    /// The player at the contained index outlasted every other player
+    Winner(usize),
+    /// Every remaining player topped out on the same frame
+    Draw,
+}
+
+/// A match between two players, whose games advance in lockstep and send
+/// their attacks to each other
+pub struct VersusMatch {
+    players: [TetrisGame; 2],
+    outcome: Option<MatchOutcome>,
+    frame: usize,
+}
+
+impl VersusMatch {
+    #[must_use]
+    /// Creates a match between the players of `first` and `second`
+    pub fn new(first: TetrisGame, second: TetrisGame) -> VersusMatch {
+        VersusMatch {
+            players: [first, second],
+            outcome: None,
+            frame: 0,
+        }
+    }
+
+    #[must_use]
+    /// Creates a match where both players play with the rules in `config`
+    pub fn with_config(config: &GameConfig) -> VersusMatch {
+        Self::new(
+            TetrisGame::with_config(config.clone()),
+            TetrisGame::with_config(config.clone()),
+        )
+    }
+
+    /// Starts both players' games
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::GameOver)` if either game has already ended
+    pub fn start(&mut self) -> Result<(), TetrisError> {
+        for player in &mut self.players {
+            player.start()?;
+        }
+        Ok(())
+    }
+
+    /// Advances both players' games by one frame, executing each player's
+    /// inputs, then sends each player's attacks to the other player. The
+    /// match ends once either player's game has ended. Both games always
+    /// advance, even if one of them returns an error, so they stay in
+    /// lockstep.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(TetrisError::GameOver)` if the match has already ended,
+    /// or the first error of `Game::next_frame`
+    pub fn next_frame(
+        &mut self,
+        inputs: [&mut VecDeque<TetrisInput>; 2],
+    ) -> Result<(), TetrisError> {
+        if self.outcome.is_some() {
+            return Err(TetrisError::GameOver);
+        }
+
+        let results: Vec<Result<(), TetrisError>> = self
+            .players
+            .iter_mut()
+            .zip(inputs)
+            .map(|(player, inputs)| player.next_frame(inputs))
+            .collect();
+        self.finish_frame();
+        results.into_iter().collect()
+    }
+
+    /// Advances the match by one frame like `VersusMatch::next_frame`, first
+    /// turning each player's key events into inputs using their handling
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `VersusMatch::next_frame`
+    pub fn next_frame_with_keys(&mut self, events: [&[KeyEvent]; 2]) -> Result<(), TetrisError> {
+        if self.outcome.is_some() {
+            return Err(TetrisError::GameOver);
+        }
+
+        let results: Vec<Result<(), TetrisError>> = self
+            .players
+            .iter_mut()
+            .zip(events)
+            .map(|(player, events)| player.next_frame_with_keys(events))
+            .collect();
+        self.finish_frame();
+        results.into_iter().collect()
+    }
+
+    /// Returns a reference to the players' games
+    #[must_use]
+    pub fn players(&self) -> &[TetrisGame; 2] {
+        &self.players
+    }
+
+    /// Returns the outcome of the match, or `None` if it is still running
+    #[must_use]
+    pub fn outcome(&self) -> Option<MatchOutcome> {
+        self.outcome
+    }
+
+    /// Returns the number of frames the match has run for
+    #[must_use]
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Sends each player's attacks to the other player, then ends the match
+    /// if either player's game has ended
+    fn finish_frame(&mut self) {
+        let [first, second] = &mut self.players;
+        for attack in first.take_outgoing_attacks() {
+            second.receive_garbage(attack);
+        }
+        for attack in second.take_outgoing_attacks() {
+            first.receive_garbage(attack);
+        }
+
+        self.outcome = match (first.game_over(), second.game_over()) {
+            (false, false) => None,
+            (false, true) => Some(MatchOutcome::Winner(0)),
+            (true, false) => Some(MatchOutcome::Winner(1)),
+            (true, true) => Some(MatchOutcome::Draw),
+        };
+        self.frame += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a started match between two players with the default rules
+    fn started_match() -> VersusMatch {
+        let mut versus = VersusMatch::with_config(&GameConfig::default());
+        versus.start().unwrap();
+        versus
+    }
+
+    #[test]
+    fn attacks_are_sent_to_the_opponent() {
+        let mut versus = started_match();
+        versus.players[0].fill_well(19);
+        versus.players[0].drop_i_in_well();
+
+        versus
+            .next_frame([&mut VecDeque::new(), &mut VecDeque::new()])
+            .unwrap();
+        assert_eq!(
+            4,
+            versus.players()[1].pending_garbage(),
+            "A quad should send 4 lines of garbage to the opponent"
+        );
+        assert_eq!(0, versus.players()[0].pending_garbage());
+    }
+
+    #[test]
+    fn forfeit_loses_the_match() {
+        let mut versus = started_match();
+        versus
+            .next_frame([
+                &mut VecDeque::new(),
+                &mut VecDeque::from([TetrisInput::Forfeit]),
+            ])
+            .unwrap();
+
+        assert_eq!(Some(MatchOutcome::Winner(0)), versus.outcome());
+        assert_eq!(
+            Err(TetrisError::GameOver),
+            versus.next_frame([&mut VecDeque::new(), &mut VecDeque::new()]),
+            "A finished match should not advance"
+        );
+        assert_eq!(1, versus.frame());
+    }
+
+    #[test]
+    fn simultaneous_top_out_is_a_draw() {
+        let mut versus = started_match();
+        versus
+            .next_frame([
+                &mut VecDeque::from([TetrisInput::Forfeit]),
+                &mut VecDeque::from([TetrisInput::Forfeit]),
+            ])
+            .unwrap();
+
+        assert_eq!(Some(MatchOutcome::Draw), versus.outcome());
+    }
+
+    #[test]
+    fn errors_do_not_stop_the_other_player() {
+        let mut versus = VersusMatch::with_config(&GameConfig::default());
+        versus.players[1].start().unwrap();
+        let start_x = versus.players()[1].active_piece().unwrap().position().x();
+
+        assert_eq!(
+            Err(TetrisError::GameNotStarted),
+            versus.next_frame([
+                &mut VecDeque::new(),
+                &mut VecDeque::from([TetrisInput::MoveLeft]),
+            ])
+        );
+        assert_eq!(
+            start_x - 1,
+            versus.players()[1].active_piece().unwrap().position().x(),
+            "The second player should advance even if the first player's frame fails"
+        );
+    }
+}